@@ -1,3 +1,14 @@
 fn main() {
+    // Expose the resolved taguchi library version to the crate
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let version = lock
+        .split("[[package]]")
+        .find(|pkg| pkg.contains("name = \"taguchi\"\n"))
+        .and_then(|pkg| pkg.lines().find_map(|line| line.strip_prefix("version = \"")))
+        .map(|v| v.trim_end_matches('"').to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=TAGUCHI_VERSION={}", version);
+
     tauri_build::build()
 }
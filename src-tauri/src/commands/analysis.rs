@@ -1,15 +1,38 @@
 //! Analysis and verification commands.
 
-use crate::types::{BalanceData, CorrelationData, VerificationData, VerificationIssue};
-use std::collections::HashMap;
+use crate::types::{
+    BalanceData, CellDifference, CorrelationData, DesignClassification, DesignDiff, DesignType,
+    QualityCard, VerificationData, VerificationIssue,
+};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
 use taguchi::oa::{OAParams, OA};
 
+/// Highest strength checked by `get_array_quality_card` for arrays that are
+/// not two-level.
+const QUALITY_CARD_MAX_STRENGTH: u32 = 4;
+
+/// Word length pattern entries below this are treated as zero.
+const RESOLUTION_EPSILON: f64 = 1e-9;
+
 /// Verify an array's strength.
 #[tauri::command]
 pub fn verify_array(data: Vec<Vec<u32>>, claimed_strength: u32) -> Result<VerificationData, String> {
     // Convert input data to OA
     let oa = data_to_oa(data)?;
 
+    // Fast parallel check; only fall back to the detailed report when it fails
+    let actual_strength = parallel_strength(&oa, claimed_strength);
+    if actual_strength >= claimed_strength {
+        return Ok(VerificationData {
+            is_valid: true,
+            claimed_strength,
+            actual_strength,
+            issues: Vec::new(),
+        });
+    }
+
     // Run verification
     let result = taguchi::verify_strength(&oa, claimed_strength).map_err(|e| e.to_string())?;
 
@@ -48,7 +71,7 @@ pub fn verify_array(data: Vec<Vec<u32>>, claimed_strength: u32) -> Result<Verifi
 #[tauri::command]
 pub fn compute_array_strength(data: Vec<Vec<u32>>, max_check: u32) -> Result<u32, String> {
     let oa = data_to_oa(data)?;
-    taguchi::compute_strength(&oa, max_check).map_err(|e| e.to_string())
+    Ok(parallel_strength(&oa, max_check))
 }
 
 /// Get balance report for an array.
@@ -75,6 +98,28 @@ pub fn get_balance_report(data: Vec<Vec<u32>>) -> Result<BalanceData, String> {
         level_counts.push(counts);
     }
 
+    // Normalized Shannon entropy (1.0 = all levels equally frequent)
+    let entropy: Vec<f64> = level_counts
+        .iter()
+        .enumerate()
+        .map(|(col, counts)| {
+            let levels = oa.levels_for(col) as f64;
+            if levels <= 1.0 {
+                return 1.0;
+            }
+            let runs = oa.runs() as f64;
+            let h: f64 = counts
+                .values()
+                .filter(|&&c| c > 0)
+                .map(|&c| {
+                    let p = c as f64 / runs;
+                    -p * p.ln()
+                })
+                .sum();
+            h / levels.ln()
+        })
+        .collect();
+
     let expected_count = if oa.factors() > 0 {
         oa.runs() / (oa.levels_for(0) as usize)
     } else {
@@ -85,6 +130,7 @@ pub fn get_balance_report(data: Vec<Vec<u32>>) -> Result<BalanceData, String> {
         factor_balance,
         level_counts,
         expected_count,
+        entropy,
     })
 }
 
@@ -110,6 +156,367 @@ pub fn get_correlation_matrix(data: Vec<Vec<u32>>) -> Result<CorrelationData, St
     Ok(CorrelationData { matrix, factors })
 }
 
+/// List every distinct level-tuple in the given columns with its occurrence count.
+#[tauri::command]
+pub fn list_covered_tuples(
+    data: Vec<Vec<u32>>,
+    columns: Vec<usize>,
+) -> Result<Vec<(Vec<u32>, usize)>, String> {
+    let oa = data_to_oa(data)?;
+
+    if columns.is_empty() {
+        return Err("At least one column must be selected".to_string());
+    }
+    if let Some(&col) = columns.iter().find(|&&c| c >= oa.factors()) {
+        return Err(format!(
+            "Column {} is out of range (array has {} columns)",
+            col,
+            oa.factors()
+        ));
+    }
+
+    // Count occurrences of each tuple, ordered lexicographically
+    let mut counts: BTreeMap<Vec<u32>, usize> = BTreeMap::new();
+    for row in 0..oa.runs() {
+        let tuple: Vec<u32> = columns.iter().map(|&col| oa.get(row, col)).collect();
+        *counts.entry(tuple).or_insert(0) += 1;
+    }
+
+    Ok(counts.into_iter().collect())
+}
+
+/// Classify an array as a full factorial, fractional factorial, Plackett-Burman,
+/// or mixed orthogonal array.
+#[tauri::command]
+pub fn classify_design(data: Vec<Vec<u32>>) -> Result<DesignClassification, String> {
+    let oa = data_to_oa(data)?;
+    let runs = oa.runs();
+    let levels = oa.levels_vec();
+
+    // Runs in the corresponding full factorial (None if it overflows)
+    let full_runs = levels
+        .iter()
+        .try_fold(1u128, |acc, &l| acc.checked_mul(l as u128));
+
+    let distinct_rows = oa
+        .rows()
+        .map(|row| row.to_vec())
+        .collect::<std::collections::HashSet<_>>()
+        .len();
+
+    let is_full = full_runs == Some(runs as u128) && distinct_rows == runs;
+    let is_mixed = levels.iter().any(|&l| l != levels[0]);
+    let is_two_level = levels.iter().all(|&l| l == 2);
+
+    let design_type = if is_full {
+        DesignType::FullFactorial
+    } else if is_mixed {
+        DesignType::MixedOrthogonalArray
+    } else if is_two_level && runs % 4 == 0 && !runs.is_power_of_two() {
+        DesignType::PlackettBurman
+    } else {
+        DesignType::FractionalFactorial
+    };
+
+    let fraction = if is_full {
+        Some("full factorial".to_string())
+    } else {
+        full_runs.map(|full| {
+            if full % runs as u128 == 0 {
+                format!("1/{} fraction", full / runs as u128)
+            } else {
+                format!("{}/{} fraction", runs, full)
+            }
+        })
+    };
+
+    let description = match design_type {
+        DesignType::FullFactorial => format!("Full factorial with {} runs", runs),
+        DesignType::FractionalFactorial => format!(
+            "Fractional factorial with {} runs ({})",
+            runs,
+            fraction.as_deref().unwrap_or("unknown fraction")
+        ),
+        DesignType::PlackettBurman => format!("Plackett-Burman design with {} runs", runs),
+        DesignType::MixedOrthogonalArray => {
+            format!("Mixed-level orthogonal array with {} runs", runs)
+        }
+    };
+
+    Ok(DesignClassification {
+        design_type,
+        fraction,
+        full_factorial_runs: full_runs.and_then(|f| u64::try_from(f).ok()),
+        description,
+    })
+}
+
+/// Maximum number of factors an array's runs and levels can support at a strength.
+///
+/// Uses the Rao bound with the smallest level count, which is an upper bound
+/// for mixed-level arrays as well.
+#[tauri::command]
+pub fn max_factors_for_strength(data: Vec<Vec<u32>>, strength: u32) -> Result<usize, String> {
+    if strength < 2 {
+        return Err("Strength must be at least 2".to_string());
+    }
+
+    let oa = data_to_oa(data)?;
+    let runs = oa.runs() as u128;
+    let levels = oa.levels_vec().iter().copied().min().unwrap_or(2);
+    if levels < 2 {
+        return Err("Every factor must have at least 2 levels".to_string());
+    }
+
+    // Largest k whose Rao bound does not exceed the run count
+    let mut max_factors = 0;
+    let mut k = strength as usize;
+    while rao_bound(k, levels, strength).is_some_and(|bound| bound <= runs) {
+        max_factors = k;
+        k += 1;
+    }
+
+    Ok(max_factors)
+}
+
+/// Compare two arrays of the same shape cell by cell.
+#[tauri::command]
+pub fn diff_designs(a: Vec<Vec<u32>>, b: Vec<Vec<u32>>) -> Result<DesignDiff, String> {
+    let cols = a.first().map(|row| row.len()).unwrap_or(0);
+    let cols_b = b.first().map(|row| row.len()).unwrap_or(0);
+    if a.iter().any(|row| row.len() != cols) || b.iter().any(|row| row.len() != cols_b) {
+        return Err("All rows must have the same number of columns".to_string());
+    }
+    if a.len() != b.len() || cols != cols_b {
+        return Err(format!(
+            "Shape mismatch: {}×{} vs {}×{}",
+            a.len(),
+            cols,
+            b.len(),
+            cols_b
+        ));
+    }
+
+    let differences: Vec<CellDifference> = a
+        .iter()
+        .zip(b.iter())
+        .enumerate()
+        .flat_map(|(row, (row_a, row_b))| {
+            row_a
+                .iter()
+                .zip(row_b.iter())
+                .enumerate()
+                .filter(|(_, (old, new))| old != new)
+                .map(move |(col, (&old, &new))| CellDifference { row, col, old, new })
+        })
+        .collect();
+
+    Ok(DesignDiff {
+        runs: a.len(),
+        factors: cols,
+        identical: differences.is_empty(),
+        differences,
+    })
+}
+
+/// Canonicalize an array's row and column order.
+///
+/// Columns are sorted by level count, then lexicographically, and rows are
+/// sorted lexicographically, alternating until stable. Canonicalization
+/// discards the original run order.
+///
+/// The sort only approximates a canonical form. The result is always a row and
+/// column permutation of the input, so two designs with equal results are
+/// permutations of each other. The converse does not hold: on designs with
+/// many symmetric columns, permuted copies can settle in different orders and
+/// compare unequal. `identify_standard_array` runs an exact isomorphism check
+/// against the catalogue.
+#[tauri::command]
+pub fn canonicalize(data: Vec<Vec<u32>>) -> Result<Vec<Vec<u32>>, String> {
+    let cols = data.first().map(|row| row.len()).unwrap_or(0);
+    if data.iter().any(|row| row.len() != cols) {
+        return Err("All rows must have the same number of columns".to_string());
+    }
+
+    let mut rows = data;
+    rows.sort();
+
+    // Column order depends on row order and vice versa, so alternate until stable.
+    for _ in 0..cols.max(1) {
+        let levels = detect_levels(&rows, cols);
+        let mut columns: Vec<(u32, Vec<u32>)> = (0..cols)
+            .map(|col| (levels[col], rows.iter().map(|row| row[col]).collect()))
+            .collect();
+        columns.sort();
+
+        let mut next: Vec<Vec<u32>> = (0..rows.len())
+            .map(|r| columns.iter().map(|(_, column)| column[r]).collect())
+            .collect();
+        next.sort();
+
+        if next == rows {
+            break;
+        }
+        rows = next;
+    }
+
+    Ok(rows)
+}
+
+/// Bundle strength, balance, correlation, and resolution into one quality summary.
+#[tauri::command]
+pub fn get_array_quality_card(data: Vec<Vec<u32>>) -> Result<QualityCard, String> {
+    let oa = data_to_oa(data.clone())?;
+    let runs = oa.runs();
+    let factors = oa.factors();
+
+    // Two-level arrays are checked to full depth so the resolution is exact
+    let is_two_level = oa.levels_vec().iter().all(|&l| l == 2);
+    let max_check = if is_two_level { factors as u32 } else { QUALITY_CARD_MAX_STRENGTH };
+    let strength = parallel_strength(&oa, max_check);
+
+    let balance = get_balance_report(data.clone())?;
+    let unbalanced_factors: Vec<usize> = balance
+        .factor_balance
+        .iter()
+        .enumerate()
+        .filter(|(_, &balanced)| !balanced)
+        .map(|(col, _)| col)
+        .collect();
+
+    let correlation = get_correlation_matrix(data)?;
+    let off_diagonal: Vec<f64> = (0..factors)
+        .flat_map(|i| (i + 1..factors).map(move |j| (i, j)))
+        .map(|(i, j)| correlation.matrix[i][j].abs())
+        .collect();
+    let max_abs_correlation = off_diagonal.iter().copied().fold(0.0, f64::max);
+    let orthogonality_defect = if off_diagonal.is_empty() {
+        0.0
+    } else {
+        (off_diagonal.iter().map(|c| c * c).sum::<f64>() / off_diagonal.len() as f64).sqrt()
+    };
+
+    // For two-level arrays the shortest word length is one more than the strength
+    let resolution = if is_two_level && (strength as usize) < factors {
+        Some(strength + 1)
+    } else {
+        None
+    };
+
+    Ok(QualityCard {
+        runs,
+        factors,
+        strength,
+        is_balanced: unbalanced_factors.is_empty(),
+        unbalanced_factors,
+        max_abs_correlation,
+        orthogonality_defect,
+        resolution,
+    })
+}
+
+/// Compute the information matrix XᵀX of the main-effects model.
+///
+/// The model matrix has an intercept column plus `levels - 1` columns per
+/// factor, coded as `"treatment"` (0/1 dummies against level 0) or
+/// `"orthogonal"` (orthonormal polynomial contrasts).
+#[tauri::command]
+pub fn get_information_matrix(data: Vec<Vec<u32>>, coding: String) -> Result<Vec<Vec<f64>>, String> {
+    let oa = data_to_oa(data)?;
+    let levels = oa.levels_vec();
+
+    let contrasts: Vec<Vec<Vec<f64>>> = match coding.as_str() {
+        "treatment" => levels.iter().map(|&s| treatment_contrasts(s)).collect(),
+        "orthogonal" => levels.iter().map(|&s| orthogonal_contrasts(s)).collect(),
+        _ => {
+            return Err(format!(
+                "Unknown coding '{}' (expected \"orthogonal\" or \"treatment\")",
+                coding
+            ))
+        }
+    };
+
+    let params = 1 + contrasts.iter().map(|c| c.first().map_or(0, |row| row.len())).sum::<usize>();
+    if params > oa.runs() {
+        return Err(format!(
+            "Model has {} parameters but only {} runs; the information matrix is rank-deficient",
+            params,
+            oa.runs()
+        ));
+    }
+
+    // Model matrix rows: intercept then each factor's contrast row for its level
+    let model: Vec<Vec<f64>> = (0..oa.runs())
+        .map(|run| {
+            let mut row = vec![1.0];
+            for (col, factor_contrasts) in contrasts.iter().enumerate() {
+                row.extend_from_slice(&factor_contrasts[oa.get(run, col) as usize]);
+            }
+            row
+        })
+        .collect();
+
+    let mut matrix = vec![vec![0.0; params]; params];
+    for row in &model {
+        for (i, &xi) in row.iter().enumerate() {
+            for (j, &xj) in row.iter().enumerate() {
+                matrix[i][j] += xi * xj;
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Compute the generalized resolution of a (possibly mixed-level) array.
+///
+/// The resolution R is the smallest `k` with a nonzero entry `A_k` of the
+/// generalized word length pattern. Each R-column set is scored by its average
+/// squared canonical correlation `A_R(set) / min df`, and
+/// `GR = R + 1 - sqrt(max score)`. For two-level arrays this is Deng and
+/// Tang's generalized resolution. Arrays with no aliasing return `factors + 1`.
+#[tauri::command]
+pub fn get_generalized_resolution(data: Vec<Vec<u32>>) -> Result<f64, String> {
+    let oa = data_to_oa(data)?;
+
+    // Contrasts scaled so each has mean square 1 over uniformly used levels
+    let contrasts: Vec<Vec<Vec<f64>>> = oa
+        .levels_vec()
+        .iter()
+        .map(|&s| {
+            let scale = (s as f64).sqrt();
+            orthogonal_contrasts(s)
+                .into_iter()
+                .map(|row| row.into_iter().map(|c| c * scale).collect())
+                .collect()
+        })
+        .collect();
+
+    for k in 1..=oa.factors() {
+        let worst = column_combinations(oa.factors(), k)
+            .iter()
+            .map(|columns| {
+                let min_df = columns
+                    .iter()
+                    .map(|&col| oa.levels_for(col).saturating_sub(1))
+                    .min()
+                    .unwrap_or(0);
+                if min_df == 0 {
+                    0.0
+                } else {
+                    word_length(&oa, &contrasts, columns) / min_df as f64
+                }
+            })
+            .fold(0.0, f64::max);
+
+        if worst > RESOLUTION_EPSILON {
+            return Ok(k as f64 + 1.0 - worst.sqrt().min(1.0));
+        }
+    }
+
+    Ok(oa.factors() as f64 + 1.0)
+}
+
 // Helper functions
 
 fn data_to_oa(data: Vec<Vec<u32>>) -> Result<OA, String> {
@@ -130,11 +537,7 @@ fn data_to_oa(data: Vec<Vec<u32>>) -> Result<OA, String> {
     }
 
     // Detect levels per factor
-    let mut levels = vec![0u32; factors];
-    for col in 0..factors {
-        let max_val = data.iter().map(|row| row[col]).max().unwrap_or(0);
-        levels[col] = max_val + 1;
-    }
+    let levels = detect_levels(&data, factors);
 
     // Create ndarray
     let flat_data: Vec<u32> = data.into_iter().flatten().collect();
@@ -147,6 +550,221 @@ fn data_to_oa(data: Vec<Vec<u32>>) -> Result<OA, String> {
     Ok(OA::new(array, params))
 }
 
+/// Strength of `data` measured against the given level counts.
+///
+/// Unlike [`compute_array_strength`], levels are not inferred from the data, so
+/// a subset of runs that never uses some level counts as unbalanced rather
+/// than as a design with fewer levels.
+pub(crate) fn strength_for_levels(
+    data: Vec<Vec<u32>>,
+    levels: Vec<u32>,
+    max_check: u32,
+) -> Result<u32, String> {
+    let runs = data.len();
+    let factors = levels.len();
+    if !data.iter().all(|row| row.len() == factors) {
+        return Err("All rows must have one column per factor".to_string());
+    }
+
+    let flat_data: Vec<u32> = data.into_iter().flatten().collect();
+    let array = ndarray::Array2::from_shape_vec((runs, factors), flat_data)
+        .map_err(|e| e.to_string())?;
+    let params = OAParams::new_mixed(runs, levels, 0).map_err(|e| e.to_string())?;
+
+    Ok(parallel_strength(&OA::new(array, params), max_check))
+}
+
+fn detect_levels(data: &[Vec<u32>], factors: usize) -> Vec<u32> {
+    (0..factors)
+        .map(|col| data.iter().map(|row| row[col]).max().unwrap_or(0) + 1)
+        .collect()
+}
+
+/// Highest strength `t <= max_check` at which every `t`-column subarray is balanced.
+///
+/// Strengths are checked bottom-up and the first failing strength ends the search.
+fn parallel_strength(oa: &OA, max_check: u32) -> u32 {
+    let max_t = (max_check as usize).min(oa.factors());
+    for t in 1..=max_t {
+        if !all_subarrays_balanced(oa, t) {
+            return (t - 1) as u32;
+        }
+    }
+    max_t as u32
+}
+
+/// Check every `t`-column subarray in parallel.
+///
+/// Workers share a violation flag so the remaining combinations are skipped
+/// as soon as one unbalanced subarray is found.
+fn all_subarrays_balanced(oa: &OA, t: usize) -> bool {
+    let combos = column_combinations(oa.factors(), t);
+    let violation = AtomicBool::new(false);
+
+    combos.par_iter().for_each(|columns| {
+        if violation.load(Ordering::Relaxed) {
+            return;
+        }
+        if !is_balanced_subarray(oa, columns) {
+            violation.store(true, Ordering::Relaxed);
+        }
+    });
+
+    !violation.load(Ordering::Relaxed)
+}
+
+/// Whether every level combination of `columns` appears equally often.
+fn is_balanced_subarray(oa: &OA, columns: &[usize]) -> bool {
+    let num_tuples: usize = columns
+        .iter()
+        .map(|&col| oa.levels_for(col) as usize)
+        .product();
+    if num_tuples == 0 || !oa.runs().is_multiple_of(num_tuples) {
+        return false;
+    }
+
+    let mut counts: HashMap<Vec<u32>, usize> = HashMap::new();
+    for row in 0..oa.runs() {
+        let tuple: Vec<u32> = columns.iter().map(|&col| oa.get(row, col)).collect();
+        *counts.entry(tuple).or_insert(0) += 1;
+    }
+
+    let expected = oa.runs() / num_tuples;
+    counts.len() == num_tuples && counts.values().all(|&count| count == expected)
+}
+
+/// All `k`-element subsets of `0..n` in lexicographic order.
+pub(crate) fn column_combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    if k == 0 || k > n {
+        return result;
+    }
+
+    let mut current: Vec<usize> = (0..k).collect();
+    loop {
+        result.push(current.clone());
+
+        // Find the rightmost index that can still be incremented
+        let mut i = k;
+        while i > 0 && current[i - 1] == n - k + i - 1 {
+            i -= 1;
+        }
+        if i == 0 {
+            return result;
+        }
+        current[i - 1] += 1;
+        for j in i..k {
+            current[j] = current[j - 1] + 1;
+        }
+    }
+}
+
+/// Contribution `A_k(columns)` of one column set to the generalized word length pattern.
+///
+/// Sums the squared normalized J-characteristics over every combination of
+/// one contrast per column.
+fn word_length(oa: &OA, contrasts: &[Vec<Vec<f64>>], columns: &[usize]) -> f64 {
+    let dims: Vec<usize> = columns
+        .iter()
+        .map(|&col| contrasts[col].first().map_or(0, |row| row.len()))
+        .collect();
+    if dims.contains(&0) {
+        return 0.0;
+    }
+
+    let runs = oa.runs() as f64;
+    let mut total = 0.0;
+    let mut choice = vec![0usize; columns.len()];
+    loop {
+        let j: f64 = (0..oa.runs())
+            .map(|run| {
+                columns
+                    .iter()
+                    .zip(&choice)
+                    .map(|(&col, &c)| contrasts[col][oa.get(run, col) as usize][c])
+                    .product::<f64>()
+            })
+            .sum();
+        total += (j / runs).powi(2);
+
+        // Advance to the next contrast combination
+        let mut i = 0;
+        while i < choice.len() {
+            choice[i] += 1;
+            if choice[i] < dims[i] {
+                break;
+            }
+            choice[i] = 0;
+            i += 1;
+        }
+        if i == choice.len() {
+            return total;
+        }
+    }
+}
+
+/// Minimum runs for an OA with `k` factors of `s` levels at strength `t` (Rao bound).
+///
+/// Returns `None` if the bound overflows.
+fn rao_bound(k: usize, s: u32, t: u32) -> Option<u128> {
+    let u = (t / 2) as usize;
+    let s1 = (s - 1) as u128;
+
+    let mut bound: u128 = 0;
+    for i in 0..=u {
+        bound = bound.checked_add(binomial(k, i)?.checked_mul(s1.checked_pow(i as u32)?)?)?;
+    }
+    if t % 2 == 1 {
+        let extra = binomial(k - 1, u)?.checked_mul(s1.checked_pow(u as u32 + 1)?)?;
+        bound = bound.checked_add(extra)?;
+    }
+
+    Some(bound)
+}
+
+fn binomial(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result.checked_mul((n - i) as u128)? / (i as u128 + 1);
+    }
+    Some(result)
+}
+
+/// Dummy coding: level `l > 0` gets a 1 in column `l - 1`; level 0 is all zeros.
+fn treatment_contrasts(levels: u32) -> Vec<Vec<f64>> {
+    let s = levels as usize;
+    (0..s)
+        .map(|level| (1..s).map(|c| if c == level { 1.0 } else { 0.0 }).collect())
+        .collect()
+}
+
+/// Orthonormal polynomial contrasts from Gram-Schmidt on powers of the level index.
+fn orthogonal_contrasts(levels: u32) -> Vec<Vec<f64>> {
+    let s = levels as usize;
+    let mut basis: Vec<Vec<f64>> = vec![vec![1.0 / (s as f64).sqrt(); s]];
+
+    for degree in 1..s {
+        let mut v: Vec<f64> = (0..s).map(|l| (l as f64).powi(degree as i32)).collect();
+        for b in &basis {
+            let dot: f64 = v.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+            for (x, y) in v.iter_mut().zip(b.iter()) {
+                *x -= dot * y;
+            }
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        basis.push(v.iter().map(|x| x / norm).collect());
+    }
+
+    // Transpose to one row per level, dropping the constant vector
+    (0..s)
+        .map(|level| basis[1..].iter().map(|b| b[level]).collect())
+        .collect()
+}
+
 fn calculate_correlation(oa: &OA, col_i: usize, col_j: usize) -> f64 {
     let n = oa.runs() as f64;
 
@@ -1,55 +1,178 @@
 //! OA Builder commands.
 
-use crate::types::{BuildRequest, ConstructionOption, LevelSpec, OAData, OAMetadata, ValidationResult};
+use crate::commands::analysis::{column_combinations, strength_for_levels};
+use crate::types::{
+    BuildAlternative, BuildError, BuildRequest, ConstructionOption, LevelSpec, OAData, OAMetadata,
+    ValidationResult,
+};
 use chrono::Utc;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, OnceLock};
+use taguchi::construct::{
+    AddelmanKempthorne, Bose, BoseBush, Bush, Constructor, HadamardPaley, HadamardSylvester,
+    RaoHamming,
+};
 use taguchi::{available_constructions, OABuilder};
 use uuid::Uuid;
 
+/// Most aliased factor sets listed in a confounding warning.
+const MAX_LISTED_WORDS: usize = 5;
+
+/// Number of built arrays kept in the build cache.
+const BUILD_CACHE_SIZE: usize = 32;
+
+/// Recently built arrays, keyed by their build request.
+static BUILD_CACHE: OnceLock<Mutex<LruCache<BuildRequest, OAData>>> = OnceLock::new();
+
+fn build_cache() -> &'static Mutex<LruCache<BuildRequest, OAData>> {
+    BUILD_CACHE.get_or_init(|| {
+        Mutex::new(LruCache::new(
+            NonZeroUsize::new(BUILD_CACHE_SIZE).expect("cache size is non-zero"),
+        ))
+    })
+}
+
 /// Build an orthogonal array with automatic algorithm selection.
+///
+/// Identical requests are served from a small LRU cache; cached arrays are
+/// returned with a fresh ID and timestamp. On failure, the error lists the
+/// closest constructions that can be built instead.
 #[tauri::command]
-pub fn build_oa(request: BuildRequest) -> Result<OAData, String> {
-    let mut builder = OABuilder::new();
-
-    // Set levels
-    match &request.levels {
-        LevelSpec::Symmetric(s) => {
-            builder = builder.levels(*s);
-        }
-        LevelSpec::Mixed(levels) => {
-            builder = builder.mixed_levels(levels.clone());
+pub fn build_oa(request: BuildRequest) -> Result<OAData, BuildError> {
+    if let Ok(mut cache) = build_cache().lock() {
+        if let Some(cached) = cache.get(&request) {
+            let mut data = cached.clone();
+            data.id = Uuid::new_v4().to_string();
+            data.metadata.created_at = Utc::now().to_rfc3339();
+            return Ok(data);
         }
     }
 
-    // Set factors and strength
-    builder = builder.factors(request.factors).strength(request.strength);
+    let data = match build_uncached(&request) {
+        Ok(data) => data,
+        Err(e) => return Err(build_error(&request, e)),
+    };
 
-    // Set minimum runs if specified
-    if let Some(min_runs) = request.min_runs {
-        builder = builder.min_runs(min_runs);
+    if let Ok(mut cache) = build_cache().lock() {
+        cache.put(request, data.clone());
     }
 
-    // Build the array
-    let oa = builder.build().map_err(|e| e.to_string())?;
-
-    // Convert to frontend-friendly format
-    let data: Vec<Vec<u32>> = (0..oa.runs())
-        .map(|r| oa.row(r).iter().copied().collect())
-        .collect();
+    Ok(data)
+}
 
-    Ok(OAData {
-        id: Uuid::new_v4().to_string(),
-        runs: oa.runs(),
-        factors: oa.factors(),
-        levels: oa.levels_vec().to_vec(),
-        strength: oa.strength(),
-        data,
-        metadata: OAMetadata {
-            name: None,
-            algorithm: detect_algorithm(&oa),
-            created_at: Utc::now().to_rfc3339(),
-            notes: None,
+/// Build an orthogonal array with a specific, named construction.
+///
+/// Accepts names with or without hyphens (e.g. "Bose-Bush" or "BoseBush").
+/// Returns an error if the construction cannot satisfy the request.
+#[tauri::command]
+pub fn build_with_construction(request: BuildRequest, construction: String) -> Result<OAData, String> {
+    let q = match &request.levels {
+        LevelSpec::Symmetric(s) => *s,
+        LevelSpec::Mixed(levels) => match levels.first() {
+            Some(&first) if levels.iter().all(|&l| l == first) => first,
+            _ => return Err("Named constructions require symmetric levels".to_string()),
         },
-    })
+    };
+    let factors = request.factors;
+    let strength = request.strength;
+    let min_runs = request.min_runs.unwrap_or(0);
+
+    let key: String = construction
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let require_strength_two = |name: &str| {
+        if strength == 2 {
+            Ok(())
+        } else {
+            Err(format!("{} only produces strength 2 arrays", name))
+        }
+    };
+
+    let (name, constructor): (&str, Box<dyn Constructor>) = match key.as_str() {
+        "bose" => {
+            require_strength_two("Bose")?;
+            if !taguchi::is_prime_power(q) {
+                return Err(format!("Bose requires a prime power number of levels, got {}", q));
+            }
+            ("Bose", Box::new(Bose::new(q)))
+        }
+        "bush" => ("Bush", Box::new(Bush::new(q, strength).map_err(|e| e.to_string())?)),
+        "bosebush" => {
+            require_strength_two("Bose-Bush")?;
+            ("Bose-Bush", Box::new(BoseBush::new(q).map_err(|e| e.to_string())?))
+        }
+        "addelmankempthorne" => {
+            require_strength_two("Addelman-Kempthorne")?;
+            (
+                "Addelman-Kempthorne",
+                Box::new(AddelmanKempthorne::new(q).map_err(|e| e.to_string())?),
+            )
+        }
+        "hadamardsylvester" => {
+            require_strength_two("Hadamard-Sylvester")?;
+            if q != 2 {
+                return Err("Hadamard-Sylvester only supports 2 levels".to_string());
+            }
+            let n = (factors + 1).max(min_runs).max(4).next_power_of_two();
+            (
+                "Hadamard-Sylvester",
+                Box::new(HadamardSylvester::new(n).map_err(|e| e.to_string())?),
+            )
+        }
+        "hadamardpaley" => {
+            require_strength_two("Hadamard-Paley")?;
+            if q != 2 {
+                return Err("Hadamard-Paley only supports 2 levels".to_string());
+            }
+            let p = (3u32..=10_000)
+                .filter(|&p| p % 4 == 3 && taguchi::is_prime(p))
+                .find(|&p| p as usize >= factors && p as usize + 1 >= min_runs)
+                .ok_or_else(|| "No Paley prime available for these parameters".to_string())?;
+            (
+                "Hadamard-Paley",
+                Box::new(HadamardPaley::new(p).map_err(|e| e.to_string())?),
+            )
+        }
+        "raohamming" => {
+            require_strength_two("Rao-Hamming")?;
+            if q < 2 {
+                return Err("Rao-Hamming requires at least 2 levels".to_string());
+            }
+            let qs = q as usize;
+            let m = (2u32..=10)
+                .find(|&m| {
+                    qs.checked_pow(m)
+                        .is_some_and(|runs| (runs - 1) / (qs - 1) >= factors && runs >= min_runs)
+                })
+                .ok_or_else(|| "No Rao-Hamming array is large enough for these parameters".to_string())?;
+            ("Rao-Hamming", Box::new(RaoHamming::new(q, m).map_err(|e| e.to_string())?))
+        }
+        _ => return Err(format!("Unknown construction '{}'", construction)),
+    };
+
+    if factors > constructor.max_factors() {
+        return Err(format!(
+            "{} supports at most {} factors, requested {}",
+            name,
+            constructor.max_factors(),
+            factors
+        ));
+    }
+    if constructor.runs() < min_runs {
+        return Err(format!(
+            "{} produces {} runs, fewer than the requested minimum of {}",
+            name,
+            constructor.runs(),
+            min_runs
+        ));
+    }
+
+    let oa = constructor.construct(factors).map_err(|e| e.to_string())?;
+    Ok(oa_to_data(&oa, name.to_string()))
 }
 
 /// Get available constructions for given parameters.
@@ -57,16 +180,29 @@ pub fn build_oa(request: BuildRequest) -> Result<OAData, String> {
 pub fn get_available_constructions(levels: u32, strength: u32) -> Vec<ConstructionOption> {
     let constructions = available_constructions(levels, strength);
 
-    constructions
+    let mut options: Vec<ConstructionOption> = constructions
         .into_iter()
         .map(|(name, runs, max_factors)| ConstructionOption {
             name: name.to_string(),
             runs,
             max_factors,
+            relative_size: 1.0, // Set below
             description: get_construction_description(&name),
             constraints: get_construction_constraints(&name, levels),
         })
-        .collect()
+        .collect();
+    set_relative_sizes(&mut options);
+    options
+}
+
+/// Get available constructions for mixed-level parameters.
+///
+/// Mixed-level arrays are built from a symmetric base array whose level count
+/// is a prime power divisible by every requested level, then collapsed per
+/// column. Each returned option describes one such base construction.
+#[tauri::command]
+pub fn get_available_constructions_mixed(levels: Vec<u32>, strength: u32) -> Vec<ConstructionOption> {
+    mixed_constructions(&levels, strength)
 }
 
 /// Validate build parameters before construction.
@@ -75,9 +211,21 @@ pub fn validate_build_params(request: BuildRequest) -> ValidationResult {
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
-    // Extract the symmetric level if applicable
-    let levels = match &request.levels {
-        LevelSpec::Symmetric(s) => *s,
+    let covering = match request.mode.as_deref() {
+        None | Some("orthogonal") => false,
+        Some("covering") => true,
+        Some(other) => {
+            errors.push(format!(
+                "Unknown build mode '{}' (expected 'orthogonal' or 'covering')",
+                other
+            ));
+            false
+        }
+    };
+
+    // Extract the symmetric level, or the full level list for mixed arrays
+    let (levels, mixed_levels) = match &request.levels {
+        LevelSpec::Symmetric(s) => (*s, None),
         LevelSpec::Mixed(lvls) => {
             if lvls.is_empty() {
                 errors.push("At least one level must be specified".to_string());
@@ -88,13 +236,21 @@ pub fn validate_build_params(request: BuildRequest) -> ValidationResult {
                     suggestions: vec![],
                 };
             }
-            // For mixed levels, use max for construction lookup
-            *lvls.iter().max().unwrap()
+            let max = *lvls.iter().max().unwrap();
+            if lvls.iter().all(|&l| l == max) {
+                (max, None)
+            } else {
+                (max, Some(lvls.clone()))
+            }
         }
     };
 
     // Basic validation
-    if levels < 2 {
+    let min_levels = mixed_levels
+        .as_ref()
+        .and_then(|lvls| lvls.iter().min().copied())
+        .unwrap_or(levels);
+    if min_levels < 2 {
         errors.push("Levels must be at least 2".to_string());
     }
 
@@ -109,35 +265,82 @@ pub fn validate_build_params(request: BuildRequest) -> ValidationResult {
         ));
     }
 
+    if let Some(max_strength) = request.max_strength {
+        if max_strength < request.strength {
+            errors.push(format!(
+                "Maximum strength {} is below the minimum strength {}",
+                max_strength, request.strength
+            ));
+        }
+    }
+
     // Check if prime power
-    if !taguchi::is_prime_power(levels) {
-        warnings.push(format!(
-            "Levels {} is not a prime power - limited constructions available",
-            levels
-        ));
+    match &mixed_levels {
+        None => {
+            if !taguchi::is_prime_power(levels) {
+                warnings.push(format!(
+                    "Levels {} is not a prime power - limited constructions available",
+                    levels
+                ));
+            }
+        }
+        Some(lvls) => {
+            if mixed_base_levels(lvls).is_empty() {
+                warnings.push(format!(
+                    "Levels {:?} share no prime power base - mixed construction unavailable",
+                    lvls
+                ));
+            }
+        }
     }
 
     // Get available constructions
     let suggestions = if errors.is_empty() {
-        available_constructions(levels, request.strength)
+        let options = match &mixed_levels {
+            None => get_available_constructions(levels, request.strength),
+            Some(lvls) => mixed_constructions(lvls, request.strength),
+        };
+        let options: Vec<ConstructionOption> = options
             .into_iter()
-            .filter(|(_, _, max_factors)| *max_factors >= request.factors)
-            .map(|(name, runs, max_factors)| ConstructionOption {
-                name: name.to_string(),
-                runs,
-                max_factors,
-                description: get_construction_description(&name),
-                constraints: get_construction_constraints(&name, levels),
-            })
-            .collect()
+            .filter(|option| option.max_factors >= request.factors)
+            .collect();
+
+        // Apply the minimum runs constraint, if any
+        let mut feasible = match request.min_runs {
+            Some(min_runs) => {
+                let largest = options.iter().map(|option| option.runs).max();
+                let feasible: Vec<ConstructionOption> = options
+                    .into_iter()
+                    .filter(|option| option.runs >= min_runs)
+                    .collect();
+                if feasible.is_empty() {
+                    if let Some(largest) = largest {
+                        warnings.push(format!(
+                            "No construction reaches {} runs; largest is {}",
+                            min_runs, largest
+                        ));
+                    }
+                }
+                feasible
+            }
+            None => options,
+        };
+        // Sizes are relative to the smallest option that survives the filters
+        set_relative_sizes(&mut feasible);
+        feasible
     } else {
         vec![]
     };
 
-    if suggestions.is_empty() && errors.is_empty() {
+    // Covering arrays are built greedily, so no construction is needed
+    if suggestions.is_empty() && errors.is_empty() && !covering {
+        let levels_desc = match &mixed_levels {
+            None => levels.to_string(),
+            Some(lvls) => format!("{:?}", lvls),
+        };
         errors.push(format!(
             "No construction available for {} levels, {} factors, strength {}",
-            levels, request.factors, request.strength
+            levels_desc, request.factors, request.strength
         ));
     }
 
@@ -149,8 +352,576 @@ pub fn validate_build_params(request: BuildRequest) -> ValidationResult {
     }
 }
 
+/// Rearrange an array's columns into a new order.
+///
+/// `new_order[i]` is the current index of the column placed at position `i`;
+/// it must be a permutation of `0..factors`. All factors are kept, so the
+/// strength is unchanged.
+#[tauri::command]
+pub fn reorder_columns(data: OAData, new_order: Vec<usize>) -> Result<OAData, String> {
+    if data.levels.len() != data.factors || data.data.iter().any(|row| row.len() != data.factors) {
+        return Err(format!("Array data does not have {} columns", data.factors));
+    }
+    if new_order.len() != data.factors {
+        return Err(format!(
+            "Column order has {} entries, expected {}",
+            new_order.len(),
+            data.factors
+        ));
+    }
+    let mut seen = vec![false; data.factors];
+    for &col in &new_order {
+        if col >= data.factors || seen[col] {
+            return Err(format!(
+                "Column order must be a permutation of 0..{}",
+                data.factors
+            ));
+        }
+        seen[col] = true;
+    }
+
+    let mut reordered = data;
+    reordered.data = reordered
+        .data
+        .iter()
+        .map(|row| new_order.iter().map(|&col| row[col]).collect())
+        .collect();
+    reordered.levels = new_order.iter().map(|&col| reordered.levels[col]).collect();
+    reordered.id = Uuid::new_v4().to_string();
+    reordered.metadata.created_at = Utc::now().to_rfc3339();
+
+    Ok(reordered)
+}
+
+/// Build the mirror design by reflecting every level: `l` becomes `(levels - 1) - l`.
+///
+/// For two-level arrays this is the classic foldover.
+#[tauri::command]
+pub fn mirror_design(data: OAData) -> Result<OAData, String> {
+    if data.levels.len() != data.factors || data.data.iter().any(|row| row.len() != data.factors) {
+        return Err(format!("Array data does not have {} columns", data.factors));
+    }
+
+    let mut mirrored = data;
+    for (run, row) in mirrored.data.iter_mut().enumerate() {
+        for (col, value) in row.iter_mut().enumerate() {
+            let levels = mirrored.levels[col];
+            if *value >= levels {
+                return Err(format!(
+                    "Run {} factor {} has level {}, but the factor has {} levels",
+                    run + 1,
+                    col + 1,
+                    value,
+                    levels
+                ));
+            }
+            *value = levels - 1 - *value;
+        }
+    }
+    mirrored.id = Uuid::new_v4().to_string();
+    mirrored.metadata.name = mirrored.metadata.name.map(|name| format!("{} (mirror)", name));
+    mirrored.metadata.created_at = Utc::now().to_rfc3339();
+
+    Ok(mirrored)
+}
+
+/// Partition a design's runs into a training set and a holdout set.
+///
+/// `holdout` lists the (0-based) runs to hold out, in the order given; the
+/// rest stay in the training set in their original order. Each part's
+/// strength is recomputed against the original level counts, so a part that
+/// misses a level is unbalanced, and the training set carries a warning when
+/// it has too few runs to estimate every main effect.
+#[tauri::command]
+pub fn split_design(data: OAData, holdout: Vec<usize>) -> Result<(OAData, OAData), String> {
+    if holdout.is_empty() {
+        return Err("Holdout must contain at least one run".to_string());
+    }
+    let mut held = vec![false; data.data.len()];
+    for &run in &holdout {
+        if run >= data.data.len() {
+            return Err(format!(
+                "Run index {} is out of range (array has {} runs)",
+                run,
+                data.data.len()
+            ));
+        }
+        if held[run] {
+            return Err(format!("Run {} is listed more than once", run));
+        }
+        held[run] = true;
+    }
+    if holdout.len() == data.data.len() {
+        return Err("Holdout cannot contain every run".to_string());
+    }
+
+    let subset = |rows: Vec<Vec<u32>>, suffix: &str| -> Result<OAData, String> {
+        let mut part = data.clone();
+        part.strength = strength_for_levels(rows.clone(), data.levels.clone(), data.strength)?;
+        part.runs = rows.len();
+        part.data = rows;
+        part.id = Uuid::new_v4().to_string();
+        part.metadata.name = part.metadata.name.map(|name| format!("{} ({})", name, suffix));
+        part.metadata.created_at = Utc::now().to_rfc3339();
+        part.warnings = vec![];
+        Ok(part)
+    };
+
+    let training_rows: Vec<Vec<u32>> = data
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(run, _)| !held[*run])
+        .map(|(_, row)| row.clone())
+        .collect();
+    let holdout_rows: Vec<Vec<u32>> = holdout.iter().map(|&run| data.data[run].clone()).collect();
+
+    let mut training = subset(training_rows, "training")?;
+    let holdout = subset(holdout_rows, "holdout")?;
+
+    // Intercept plus levels - 1 per factor
+    let params = 1 + data.levels.iter().map(|&l| l.saturating_sub(1) as usize).sum::<usize>();
+    if training.runs < params {
+        training.warnings.push(format!(
+            "Training set has {} runs but the main-effects model needs {}; not every effect can be estimated",
+            training.runs, params
+        ));
+    } else if training.runs == params {
+        training.warnings.push(
+            "Training set leaves zero error degrees of freedom; F-tests will be unavailable".to_string(),
+        );
+    }
+
+    Ok((training, holdout))
+}
+
 // Helper functions
 
+/// Build an array, trying higher strengths up to `max_strength` when set.
+///
+/// A higher strength is only used if it needs no more runs than the array at
+/// the requested strength, or than `min_runs` when that is larger.
+fn build_uncached(request: &BuildRequest) -> Result<OAData, String> {
+    match request.mode.as_deref() {
+        None | Some("orthogonal") => {}
+        Some("covering") => return build_covering(request),
+        Some(other) => return Err(format!("Unknown build mode '{}'", other)),
+    }
+
+    let mut oa = build_at_strength(request, request.strength)?;
+
+    if let Some(max_strength) = request.max_strength {
+        let budget = oa.runs().max(request.min_runs.unwrap_or(0));
+        for strength in (request.strength + 1..=max_strength).rev() {
+            if let Ok(candidate) = build_at_strength(request, strength) {
+                if candidate.runs() <= budget {
+                    oa = candidate;
+                    break;
+                }
+            }
+        }
+    }
+
+    let algorithm = detect_algorithm(&oa);
+    let mut data = oa_to_data(&oa, algorithm);
+
+    // Report anything that differs from what was requested
+    let max_strength = request.max_strength.unwrap_or(request.strength).max(request.strength);
+    if !(request.strength..=max_strength).contains(&oa.strength()) {
+        data.warnings.push(format!(
+            "Requested strength {} but the built array has strength {}",
+            request.strength,
+            oa.strength()
+        ));
+    }
+    if let LevelSpec::Mixed(levels) = &request.levels {
+        if levels.iter().any(|&l| l != levels[0]) {
+            data.warnings.push(
+                "Mixed levels were produced by collapsing the levels of a symmetric base array"
+                    .to_string(),
+            );
+        }
+    }
+    if data.metadata.algorithm == "Unknown" {
+        data.warnings.push("The construction algorithm could not be identified".to_string());
+    }
+    if oa.levels_vec().iter().all(|&l| l == 2) {
+        data.warnings.extend(confounding_warnings(request, &data.data));
+    }
+
+    Ok(data)
+}
+
+/// Build a covering array: every `strength`-tuple of levels appears at least once.
+///
+/// Uses the greedy construction unless an orthogonal array for the same
+/// request is no larger. Rows are repeated to reach `min_runs` if needed.
+fn build_covering(request: &BuildRequest) -> Result<OAData, String> {
+    let levels = match &request.levels {
+        LevelSpec::Symmetric(s) => vec![*s; request.factors],
+        LevelSpec::Mixed(levels) => {
+            if levels.len() != request.factors {
+                return Err(format!(
+                    "Expected {} level counts, got {}",
+                    request.factors,
+                    levels.len()
+                ));
+            }
+            levels.clone()
+        }
+    };
+    if levels.iter().any(|&l| l < 2) {
+        return Err("Levels must be at least 2".to_string());
+    }
+    if request.strength < 1 || request.strength as usize > request.factors {
+        return Err(format!(
+            "Strength must be between 1 and the number of factors ({})",
+            request.factors
+        ));
+    }
+
+    let rows = greedy_covering_rows(&levels, request.strength as usize);
+
+    // An orthogonal array also covers every tuple, so prefer it when no larger
+    if let Ok(oa) = build_at_strength(request, request.strength) {
+        if oa.runs() <= rows.len() {
+            let algorithm = detect_algorithm(&oa);
+            return Ok(oa_to_data(&oa, algorithm));
+        }
+    }
+
+    let mut data: Vec<Vec<u32>> = rows;
+    let covering_runs = data.len();
+    let min_runs = request.min_runs.unwrap_or(0);
+    let mut next = 0;
+    while data.len() < min_runs {
+        data.push(data[next].clone());
+        next += 1;
+    }
+
+    let mut warnings = vec![format!(
+        "Covering array: every {}-tuple appears at least once, but not equally often; \
+         ANOVA and main effects assume balance and may be biased",
+        request.strength
+    )];
+    if data.len() > covering_runs {
+        warnings.push(format!(
+            "{} rows were repeated to reach the minimum of {} runs",
+            data.len() - covering_runs,
+            min_runs
+        ));
+    }
+
+    Ok(OAData {
+        id: Uuid::new_v4().to_string(),
+        runs: data.len(),
+        factors: request.factors,
+        levels,
+        strength: request.strength,
+        data,
+        metadata: OAMetadata {
+            name: None,
+            algorithm: "Greedy Covering".to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            notes: None,
+        },
+        warnings,
+    })
+}
+
+/// Greedy one-row-at-a-time covering array construction.
+///
+/// Each row is seeded with the first uncovered tuple; the remaining columns
+/// take the level that covers the most still-uncovered tuples.
+fn greedy_covering_rows(levels: &[u32], strength: usize) -> Vec<Vec<u32>> {
+    let factors = levels.len();
+    let combos = column_combinations(factors, strength);
+
+    // Tuple index of `values` (taken from `row`) within a combination, mixed radix
+    let tuple_index = |combo: &[usize], row: &[u32]| -> usize {
+        combo
+            .iter()
+            .fold(0, |acc, &col| acc * levels[col] as usize + row[col] as usize)
+    };
+
+    let mut uncovered: Vec<Vec<bool>> = combos
+        .iter()
+        .map(|combo| {
+            let size: usize = combo.iter().map(|&col| levels[col] as usize).product();
+            vec![true; size]
+        })
+        .collect();
+    let mut remaining: usize = uncovered.iter().map(|u| u.len()).sum();
+
+    let mut combos_by_column: Vec<Vec<usize>> = vec![Vec::new(); factors];
+    for (i, combo) in combos.iter().enumerate() {
+        for &col in combo {
+            combos_by_column[col].push(i);
+        }
+    }
+
+    let mut rows = Vec::new();
+    while remaining > 0 {
+        let mut row = vec![0u32; factors];
+        let mut assigned = vec![false; factors];
+
+        // Seed with the first uncovered tuple
+        let seed = uncovered
+            .iter()
+            .enumerate()
+            .find_map(|(i, u)| u.iter().position(|&open| open).map(|idx| (i, idx)));
+        if let Some((i, mut idx)) = seed {
+            for &col in combos[i].iter().rev() {
+                let s = levels[col] as usize;
+                row[col] = (idx % s) as u32;
+                assigned[col] = true;
+                idx /= s;
+            }
+        }
+
+        // Fill the remaining columns greedily
+        for col in 0..factors {
+            if assigned[col] {
+                continue;
+            }
+            assigned[col] = true;
+            let mut best_level = 0;
+            let mut best_gain = 0;
+            for level in 0..levels[col] {
+                row[col] = level;
+                let gain = combos_by_column[col]
+                    .iter()
+                    .filter(|&&i| {
+                        combos[i].iter().all(|&c| assigned[c])
+                            && uncovered[i][tuple_index(&combos[i], &row)]
+                    })
+                    .count();
+                if gain > best_gain {
+                    best_gain = gain;
+                    best_level = level;
+                }
+            }
+            row[col] = best_level;
+        }
+
+        for (i, combo) in combos.iter().enumerate() {
+            let idx = tuple_index(combo, &row);
+            if uncovered[i][idx] {
+                uncovered[i][idx] = false;
+                remaining -= 1;
+            }
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Warn when a two-level design aliases main effects with two-factor interactions.
+///
+/// Three columns whose levels XOR to a constant form a defining word of length
+/// 3 (resolution III). The builder takes the leading columns of its base array,
+/// so if a different choice of columns from the full-width base array avoids
+/// all such words, it is suggested.
+fn confounding_warnings(request: &BuildRequest, data: &[Vec<u32>]) -> Vec<String> {
+    let factors = data.first().map_or(0, |row| row.len());
+    let words: Vec<Vec<usize>> = column_combinations(factors, 3)
+        .into_iter()
+        .filter(|cols| is_defining_word(data, cols))
+        .collect();
+    if words.is_empty() {
+        return vec![];
+    }
+
+    let mut listed: Vec<String> = words
+        .iter()
+        .take(MAX_LISTED_WORDS)
+        .map(|cols| {
+            let names: Vec<String> = cols.iter().map(|col| (col + 1).to_string()).collect();
+            format!("({})", names.join(", "))
+        })
+        .collect();
+    if words.len() > MAX_LISTED_WORDS {
+        listed.push(format!("and {} more", words.len() - MAX_LISTED_WORDS));
+    }
+    let mut warnings = vec![format!(
+        "Resolution III: main effects are aliased with two-factor interactions in factor sets {}",
+        listed.join(", ")
+    )];
+
+    if let Some(columns) = resolution_iv_columns(request, data) {
+        let names: Vec<String> = columns.iter().map(|col| (col + 1).to_string()).collect();
+        warnings.push(format!(
+            "Assigning the factors to columns {} of the full {}-column base array avoids this \
+             (resolution IV)",
+            names.join(", "),
+            data.len() - 1
+        ));
+    }
+
+    warnings
+}
+
+/// Whether the given two-level columns XOR to the same value in every run.
+fn is_defining_word(data: &[Vec<u32>], cols: &[usize]) -> bool {
+    let parity = |row: &Vec<u32>| cols.iter().fold(0, |acc, &col| acc ^ row[col]);
+    match data.first() {
+        Some(first) => {
+            let expected = parity(first);
+            data.iter().all(|row| parity(row) == expected)
+        }
+        None => false,
+    }
+}
+
+/// Greedily pick columns of the full-width base array with no defining words of
+/// length 3.
+///
+/// Returns `None` if the base array cannot be rebuilt with the same leading
+/// columns, or if too few compatible columns exist.
+fn resolution_iv_columns(request: &BuildRequest, data: &[Vec<u32>]) -> Option<Vec<usize>> {
+    let factors = data.first()?.len();
+    let full_width = data.len().checked_sub(1)?;
+    if full_width <= factors {
+        return None;
+    }
+
+    let full_request = BuildRequest {
+        levels: LevelSpec::Symmetric(2),
+        factors: full_width,
+        ..request.clone()
+    };
+    let full = build_at_strength(&full_request, 2).ok()?;
+    // Check the run count first: comparing prefixes indexes `full` by run
+    if full.runs() != data.len() {
+        return None;
+    }
+    let same_prefix = (0..data.len()).all(|r| full.row(r).iter().take(factors).eq(&data[r]));
+    if !same_prefix {
+        return None;
+    }
+    let full_data: Vec<Vec<u32>> = (0..full.runs())
+        .map(|r| full.row(r).iter().copied().collect())
+        .collect();
+
+    let mut chosen: Vec<usize> = Vec::new();
+    for col in 0..full.factors() {
+        let creates_word = column_combinations(chosen.len(), 2)
+            .iter()
+            .any(|pair| is_defining_word(&full_data, &[chosen[pair[0]], chosen[pair[1]], col]));
+        if !creates_word {
+            chosen.push(col);
+            if chosen.len() == factors {
+                return Some(chosen);
+            }
+        }
+    }
+
+    None
+}
+
+/// Attach the three closest feasible alternatives to a build failure.
+fn build_error(request: &BuildRequest, message: String) -> BuildError {
+    let alternatives = closest_alternatives(request, 3);
+    if alternatives.is_empty() {
+        return BuildError::from(message);
+    }
+
+    let listed: Vec<String> = alternatives
+        .iter()
+        .map(|alt| {
+            format!(
+                "{} ({} runs, {} factors, strength {})",
+                alt.name, alt.runs, alt.max_factors, alt.strength
+            )
+        })
+        .collect();
+    BuildError {
+        message: format!(
+            "{}; closest options: {}",
+            message.trim_end_matches('.'),
+            listed.join(", ")
+        ),
+        alternatives,
+    }
+}
+
+/// Constructions at the requested levels and up to the requested strength,
+/// ranked by missing factor capacity, then lost strength, then run count.
+fn closest_alternatives(request: &BuildRequest, count: usize) -> Vec<BuildAlternative> {
+    let mut alternatives: Vec<BuildAlternative> = (1..=request.strength)
+        .flat_map(|strength| {
+            let options = match &request.levels {
+                LevelSpec::Symmetric(s) => get_available_constructions(*s, strength),
+                LevelSpec::Mixed(levels) => mixed_constructions(levels, strength),
+            };
+            options.into_iter().map(move |option| BuildAlternative {
+                name: option.name,
+                runs: option.runs,
+                max_factors: option.max_factors,
+                strength,
+            })
+        })
+        .collect();
+
+    alternatives.sort_by_key(|alt| {
+        (
+            request.factors.saturating_sub(alt.max_factors),
+            request.strength - alt.strength,
+            alt.runs,
+        )
+    });
+    alternatives.truncate(count);
+    alternatives
+}
+
+fn build_at_strength(request: &BuildRequest, strength: u32) -> Result<taguchi::oa::OA, String> {
+    let mut builder = OABuilder::new();
+
+    // Set levels
+    match &request.levels {
+        LevelSpec::Symmetric(s) => {
+            builder = builder.levels(*s);
+        }
+        LevelSpec::Mixed(levels) => {
+            builder = builder.mixed_levels(levels.clone());
+        }
+    }
+
+    // Set factors and strength
+    builder = builder.factors(request.factors).strength(strength);
+
+    // Set minimum runs if specified
+    if let Some(min_runs) = request.min_runs {
+        builder = builder.min_runs(min_runs);
+    }
+
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn oa_to_data(oa: &taguchi::oa::OA, algorithm: String) -> OAData {
+    // Convert to frontend-friendly format
+    let data: Vec<Vec<u32>> = (0..oa.runs())
+        .map(|r| oa.row(r).iter().copied().collect())
+        .collect();
+
+    OAData {
+        id: Uuid::new_v4().to_string(),
+        runs: oa.runs(),
+        factors: oa.factors(),
+        levels: oa.levels_vec().to_vec(),
+        strength: oa.strength(),
+        data,
+        metadata: OAMetadata {
+            name: None,
+            algorithm,
+            created_at: Utc::now().to_rfc3339(),
+            notes: None,
+        },
+        warnings: vec![],
+    }
+}
+
 fn detect_algorithm(oa: &taguchi::oa::OA) -> String {
     // Heuristic based on array properties
     let runs = oa.runs();
@@ -181,6 +952,48 @@ fn detect_algorithm(oa: &taguchi::oa::OA) -> String {
     "Unknown".to_string()
 }
 
+/// Prime power base levels from which every requested level can be collapsed.
+///
+/// Mirrors the search range used by the library's mixed-level builder.
+fn mixed_base_levels(levels: &[u32]) -> Vec<u32> {
+    let max = match levels.iter().max() {
+        Some(&max) if max >= 2 => max,
+        _ => return vec![],
+    };
+
+    (max..=256)
+        .filter(|&q| taguchi::is_prime_power(q) && levels.iter().all(|&s| s > 0 && q % s == 0))
+        .collect()
+}
+
+fn mixed_constructions(levels: &[u32], strength: u32) -> Vec<ConstructionOption> {
+    let mut options: Vec<ConstructionOption> = mixed_base_levels(levels)
+        .into_iter()
+        .filter(|&q| q.checked_pow(strength).is_some())
+        .flat_map(|q| {
+            get_available_constructions(q, strength)
+                .into_iter()
+                .map(move |mut option| {
+                    option.constraints.push(format!(
+                        "Built from {}-level base array with levels collapsed to {:?}",
+                        q, levels
+                    ));
+                    option
+                })
+        })
+        .collect();
+    set_relative_sizes(&mut options);
+    options
+}
+
+/// Set each option's run count relative to the smallest option.
+fn set_relative_sizes(options: &mut [ConstructionOption]) {
+    let min_runs = options.iter().map(|o| o.runs).min().unwrap_or(1).max(1);
+    for option in options.iter_mut() {
+        option.relative_size = option.runs as f64 / min_runs as f64;
+    }
+}
+
 fn get_construction_description(name: &str) -> String {
     match name {
         "Bose" => "Primary construction for strength 2 arrays".to_string(),
@@ -224,3 +1037,61 @@ fn get_construction_constraints(name: &str, levels: u32) -> Vec<String> {
 
     constraints
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether every `strength`-tuple of levels appears in some row.
+    fn covers_all_tuples(rows: &[Vec<u32>], levels: &[u32], strength: usize) -> bool {
+        column_combinations(levels.len(), strength).iter().all(|combo| {
+            let size: usize = combo.iter().map(|&col| levels[col] as usize).product();
+            let covered: std::collections::HashSet<Vec<u32>> = rows
+                .iter()
+                .map(|row| combo.iter().map(|&col| row[col]).collect())
+                .collect();
+            covered.len() == size
+        })
+    }
+
+    #[test]
+    fn greedy_covering_covers_every_tuple() {
+        let levels = [3, 2, 2, 2, 2];
+        let rows = greedy_covering_rows(&levels, 2);
+        assert!(covers_all_tuples(&rows, &levels, 2));
+        // Every level pair of the two largest factors needs its own row
+        assert!(rows.len() >= 6);
+        assert!(rows.len() < 3 * 2 * 2 * 2 * 2);
+
+        let levels = [2; 6];
+        let rows = greedy_covering_rows(&levels, 3);
+        assert!(covers_all_tuples(&rows, &levels, 3));
+    }
+
+    #[test]
+    fn resolution_iv_columns_avoid_length_three_words() {
+        let request = BuildRequest {
+            levels: LevelSpec::Symmetric(2),
+            factors: 4,
+            strength: 2,
+            max_strength: None,
+            min_runs: None,
+            mode: None,
+        };
+        let oa = build_at_strength(&request, 2).unwrap();
+        let data: Vec<Vec<u32>> = (0..oa.runs()).map(|r| oa.row(r).to_vec()).collect();
+        // The leading four columns of L8 contain the word (1, 2, 3)
+        assert!(is_defining_word(&data, &[0, 1, 2]));
+
+        let columns = resolution_iv_columns(&request, &data).unwrap();
+        assert_eq!(columns, vec![0, 1, 3, 6]);
+
+        let full_request = BuildRequest { factors: 7, ..request };
+        let full = build_at_strength(&full_request, 2).unwrap();
+        let full_data: Vec<Vec<u32>> = (0..full.runs()).map(|r| full.row(r).to_vec()).collect();
+        for triple in column_combinations(columns.len(), 3) {
+            let cols: Vec<usize> = triple.iter().map(|&i| columns[i]).collect();
+            assert!(!is_defining_word(&full_data, &cols));
+        }
+    }
+}
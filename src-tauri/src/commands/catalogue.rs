@@ -2,6 +2,7 @@
 
 use crate::types::{OAData, OAMetadata, StandardArrayInfo};
 use chrono::Utc;
+use std::collections::HashMap;
 use taguchi::get_standard_oa;
 use uuid::Uuid;
 
@@ -69,6 +70,7 @@ pub fn get_standard_array(name: String) -> Result<OAData, String> {
             created_at: Utc::now().to_rfc3339(),
             notes: None,
         },
+        warnings: vec![],
     })
 }
 
@@ -115,3 +117,279 @@ pub fn search_catalogue(
         })
         .collect()
 }
+
+/// List the standard arrays with exactly `runs` runs.
+///
+/// Returns an empty list if no catalogue array has that size.
+#[tauri::command]
+pub fn get_array_by_size(runs: usize) -> Result<Vec<StandardArrayInfo>, String> {
+    if runs == 0 {
+        return Err("Runs must be at least 1".to_string());
+    }
+
+    Ok(search_catalogue(Some(runs), Some(runs), None, None))
+}
+
+/// List the catalogue arrays an array matches up to row and column order.
+///
+/// Each catalogue array is tested for isomorphism: a column permutation
+/// (keeping level counts) is searched for under which both arrays have the
+/// same multiset of rows, so permuted runs or factors still match; relabelled
+/// levels do not. Returns an empty list if nothing matches.
+#[tauri::command]
+pub fn identify_standard_array(data: Vec<Vec<u32>>) -> Result<Vec<String>, String> {
+    let runs = data.len();
+    let factors = data.first().map(|row| row.len()).unwrap_or(0);
+    if data.iter().any(|row| row.len() != factors) {
+        return Err("All rows must have the same number of columns".to_string());
+    }
+
+    let mut matches = Vec::new();
+    for &(name, _, _, _, _, _) in STANDARD_ARRAYS {
+        let oa = match get_standard_oa(name) {
+            Ok(oa) => oa,
+            Err(_) => continue,
+        };
+        if oa.runs() != runs || oa.factors() != factors {
+            continue;
+        }
+
+        let standard: Vec<Vec<u32>> = (0..oa.runs())
+            .map(|r| oa.row(r).iter().copied().collect())
+            .collect();
+        if is_isomorphic(&data, &standard) {
+            matches.push(name.to_string());
+        }
+    }
+
+    Ok(matches)
+}
+
+// Helper functions
+
+/// Whether two same-shape arrays are equal up to row and column permutation.
+fn is_isomorphic(a: &[Vec<u32>], b: &[Vec<u32>]) -> bool {
+    let factors = a.first().map_or(0, |row| row.len());
+
+    // Take the columns of `a` in an order that keeps each prefix projection as
+    // small as possible, so dependent columns come early and prune the search
+    let a = constrained_column_order(a, factors);
+    let a_levels = column_levels(&a, factors);
+    let b_levels = column_levels(b, factors);
+
+    let mut a_sorted = a_levels.clone();
+    let mut b_sorted = b_levels.clone();
+    a_sorted.sort_unstable();
+    b_sorted.sort_unstable();
+    if a_sorted != b_sorted {
+        return false;
+    }
+
+    // Row classes of `a` after each prefix of columns, and the sorted
+    // (class, level) pairs each column must reproduce at its depth
+    let mut a_classes = vec![vec![0; a.len()]];
+    let mut targets = Vec::with_capacity(factors);
+    for col in 0..factors {
+        let (pairs, classes) = refine_classes(&a, &a_classes[col], col);
+        targets.push(pairs);
+        a_classes.push(classes);
+    }
+
+    let mut used = vec![false; factors];
+    match_columns(
+        &a,
+        b,
+        &a_levels,
+        &b_levels,
+        &targets,
+        &a_classes,
+        &vec![0; b.len()],
+        &mut used,
+    )
+}
+
+/// Reorder columns greedily so each one adds as few distinct prefix rows as possible.
+fn constrained_column_order(data: &[Vec<u32>], factors: usize) -> Vec<Vec<u32>> {
+    let mut order: Vec<usize> = Vec::with_capacity(factors);
+    let mut remaining: Vec<usize> = (0..factors).collect();
+    while !remaining.is_empty() {
+        let (pos, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(pos, &col)| {
+                order.push(col);
+                let mut rows = sorted_projection(data, &order);
+                rows.dedup();
+                order.pop();
+                (pos, rows.len())
+            })
+            .min_by_key(|&(_, distinct)| distinct)
+            .unwrap_or((0, 0));
+        order.push(remaining.remove(pos));
+    }
+
+    data.iter()
+        .map(|row| order.iter().map(|&col| row[col]).collect())
+        .collect()
+}
+
+/// Number of levels in each column, taken as the largest level plus one.
+fn column_levels(data: &[Vec<u32>], factors: usize) -> Vec<u32> {
+    (0..factors)
+        .map(|col| data.iter().map(|row| row[col]).max().unwrap_or(0) + 1)
+        .collect()
+}
+
+/// Backtracking search assigning a column of `b` to each column of `a`.
+///
+/// Rows are grouped into classes by their levels in the columns assigned so
+/// far; a column of `b` is kept only if it splits those classes exactly as the
+/// next column of `a` does. Once every row is in its own class the row
+/// correspondence is fixed, so the remaining columns are matched directly
+/// instead of searched.
+#[allow(clippy::too_many_arguments)]
+fn match_columns(
+    a: &[Vec<u32>],
+    b: &[Vec<u32>],
+    a_levels: &[u32],
+    b_levels: &[u32],
+    targets: &[Vec<(usize, u32)>],
+    a_classes: &[Vec<usize>],
+    b_classes: &[usize],
+    used: &mut [bool],
+) -> bool {
+    let depth = used.iter().filter(|&&taken| taken).count();
+    if depth == a_levels.len() {
+        return true;
+    }
+
+    let distinct = targets[depth].windows(2).all(|pair| pair[0] != pair[1]);
+    for col in 0..b_levels.len() {
+        if used[col] || b_levels[col] != a_levels[depth] {
+            continue;
+        }
+
+        let (pairs, classes) = refine_classes(b, b_classes, col);
+        if pairs != targets[depth] {
+            continue;
+        }
+
+        used[col] = true;
+        let matched = if distinct {
+            match_remaining_columns(a, b, depth + 1, &a_classes[depth + 1], &classes, used)
+        } else {
+            match_columns(a, b, a_levels, b_levels, targets, a_classes, &classes, used)
+        };
+        if matched {
+            return true;
+        }
+        used[col] = false;
+    }
+
+    false
+}
+
+/// Split row classes by the levels in one column.
+///
+/// Returns the sorted (class, level) pairs and each row's new class, numbered
+/// by the rank of its pair so that matching arrays number classes alike.
+fn refine_classes(
+    data: &[Vec<u32>],
+    classes: &[usize],
+    col: usize,
+) -> (Vec<(usize, u32)>, Vec<usize>) {
+    let pairs: Vec<(usize, u32)> = data
+        .iter()
+        .zip(classes)
+        .map(|(row, &class)| (class, row[col]))
+        .collect();
+    let mut sorted = pairs.clone();
+    sorted.sort_unstable();
+    let mut keys = sorted.clone();
+    keys.dedup();
+
+    let refined = pairs
+        .iter()
+        .map(|pair| keys.binary_search(pair).unwrap_or_else(|pos| pos))
+        .collect();
+    (sorted, refined)
+}
+
+/// Match the columns of `a` from `prefix` on once every row has its own class.
+///
+/// Rows in the same class correspond, so every remaining column of `a`,
+/// reordered by class, must equal a distinct unused column of `b`.
+fn match_remaining_columns(
+    a: &[Vec<u32>],
+    b: &[Vec<u32>],
+    prefix: usize,
+    a_classes: &[usize],
+    b_classes: &[usize],
+    used: &[bool],
+) -> bool {
+    let by_class = |data: &[Vec<u32>], classes: &[usize], col: usize| -> Vec<u32> {
+        let mut column = vec![0; data.len()];
+        for (row, &class) in data.iter().zip(classes) {
+            column[class] = row[col];
+        }
+        column
+    };
+
+    let mut available: HashMap<Vec<u32>, usize> = HashMap::new();
+    for col in (0..used.len()).filter(|&col| !used[col]) {
+        *available.entry(by_class(b, b_classes, col)).or_insert(0) += 1;
+    }
+
+    (prefix..used.len()).all(|col| {
+        match available.get_mut(&by_class(a, a_classes, col)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Rows restricted to the given columns, in sorted order.
+fn sorted_projection(data: &[Vec<u32>], cols: &[usize]) -> Vec<Vec<u32>> {
+    let mut rows: Vec<Vec<u32>> = data
+        .iter()
+        .map(|row| cols.iter().map(|&col| row[col]).collect())
+        .collect();
+    rows.sort_unstable();
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard_rows(name: &str) -> Vec<Vec<u32>> {
+        get_standard_array(name.to_string()).unwrap().data
+    }
+
+    #[test]
+    fn permuted_l8_is_identified_as_l8() {
+        let l8 = standard_rows("L8");
+        let permutation = [6, 2, 4, 0, 5, 1, 3];
+        let permuted: Vec<Vec<u32>> = l8
+            .iter()
+            .rev()
+            .map(|row| permutation.iter().map(|&col| row[col]).collect())
+            .collect();
+
+        assert!(is_isomorphic(&permuted, &l8));
+        assert_eq!(identify_standard_array(permuted).unwrap(), vec!["L8".to_string()]);
+    }
+
+    #[test]
+    fn altered_array_is_not_isomorphic() {
+        let l9 = standard_rows("L9");
+        let mut altered = l9.clone();
+        altered[0][3] = (altered[0][3] + 1) % 3;
+
+        assert!(!is_isomorphic(&altered, &l9));
+        assert!(identify_standard_array(altered).unwrap().is_empty());
+    }
+}
@@ -6,28 +6,908 @@
 use std::collections::HashMap;
 
 use ndarray::Array2;
+use sha2::{Digest, Sha256};
 use taguchi::doe::{self, AnalysisConfig, OptimizationType as LibOptType};
 use taguchi::oa::{OA, OAParams};
 
+use crate::commands::export::find_center_points;
+use crate::commands::planning::f_upper_tail;
 use crate::types::{
-    ANOVAEntry, ANOVAResult, ConfidenceInterval, DOEAnalysis, DOEAnalysisRequest, MainEffect,
-    OptimalSettings, OptimizationType, SNRatioEffect,
+    ANOVAEntry, ANOVAResult, ConfidenceInterval, CurvatureTest, DOEAnalysis, DOEAnalysisRequest, DoeValidation,
+    EffectsPlotBands, EffectsPlotFactor, FactorImportance, FactorSE, InteractionPlotData,
+    MainEffect, NestedANOVAEntry, OptimalSettings, OptimizationType, PolynomialComponent,
+    PolynomialContrasts, PoolingSuggestion, PrecheckReport, ResponseData, ResponseTable,
+    ResponseTableRow, SNFormula, SNRatioEffect, VarianceComponents,
 };
 
 /// Main entry point for DOE analysis
 #[tauri::command]
 pub fn run_doe_analysis(request: DOEAnalysisRequest) -> Result<DOEAnalysis, String> {
+    let response_data = request.response_data.to_replicates();
+
+    let oa = request_to_oa(&request, &response_data)?;
+    let config = analysis_config(&request);
+    let mut analysis_warnings = validate_response_values(&response_data, &request.optimization_type)?;
+    analysis_warnings.extend(validate_target(&request)?);
+    let sort_by = validate_sort_by(&request)?;
+    validate_sn_formula(&request, &response_data)?;
+
+    // Run analysis using the library
+    let mut lib_result = doe::analyze(&oa, &response_data, &config)
+        .map_err(|e| format!("Analysis failed: {}", e))?;
+
+    // Recompute S/N results if a custom formula was requested
+    if let Some(SNFormula::Custom { db_scale, use_variance }) = request.sn_formula {
+        apply_custom_sn(&mut lib_result, &oa, &response_data, &request, db_scale, use_variance);
+    }
+
+    // Map library results to UI types
+    let main_effects = map_main_effects(&lib_result.main_effects, &request, lib_result.grand_mean)?;
+    let sn_ratio_effects = map_sn_ratio_effects(&lib_result.sn_ratio_effects, &request.factor_ids, &request.factor_names)?;
+    let mut optimal_settings = map_optimal_settings(
+        &lib_result.optimal_settings,
+        &lib_result.sn_ratio_effects,
+        &request.factor_ids,
+    );
+    let (sn_optimal_settings, mean_adjustment_factor) = two_step_settings(&main_effects, &sn_ratio_effects);
+    optimal_settings.sn_optimal_settings = sn_optimal_settings;
+    optimal_settings.mean_adjustment_factor = mean_adjustment_factor;
+
+    if matches!(request.response_data, ResponseData::RunMeans(_)) {
+        analysis_warnings.push(
+            "Responses were provided as run means only; S/N ratios and ANOVA error have no within-run degrees of freedom.".to_string(),
+        );
+    }
+
+    // A failed ANOVA should not discard the main effects and S/N ratios
+    let anova = match check_anova(&lib_result.anova)
+        .and_then(|_| map_anova_result(&lib_result.anova, &request.factor_ids, &request.factor_names))
+    {
+        Ok(mut anova) => {
+            if let Some(groups) = &request.factor_groups {
+                anova.nested = nested_anova(&oa, &response_data, &request, groups, &anova)?;
+            }
+            sort_anova_entries(&mut anova.entries, sort_by);
+            if anova.error_df == 0 {
+                analysis_warnings.push(zero_error_df_message("F-tests"));
+            }
+            anova
+        }
+        Err(e) => {
+            analysis_warnings.push(format!(
+                "ANOVA failed: {}. Main effects and S/N ratios are still valid.",
+                e
+            ));
+            ANOVAResult::default()
+        }
+    };
+    let (r_squared, adj_r_squared) = model_fit(&anova);
+
+    Ok(DOEAnalysis {
+        config_id: String::new(), // Will be set by frontend
+        grand_mean: lib_result.grand_mean,
+        sn_grand_mean: lib_result.sn_grand_mean,
+        sn_formula_used: sn_formula_description(&request),
+        main_effects,
+        sn_ratio_effects,
+        anova,
+        optimal_settings,
+        r_squared,
+        adj_r_squared,
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+        input_hash: input_hash(&request, &response_data, &config, sort_by),
+        analysis_warnings,
+    })
+}
+
+/// Suggest which factors to pool into error, smallest contribution first.
+///
+/// Factors are pooled until the error degrees of freedom reach at least half
+/// of the total, while keeping `min_unpooled_factors` factors unpooled. Every
+/// factor is returned in ascending contribution order; `error_df` is the error
+/// degrees of freedom if that factor and all smaller ones were pooled.
+#[tauri::command]
+pub fn suggest_pooling(request: DOEAnalysisRequest) -> Result<Vec<PoolingSuggestion>, String> {
+    let response_data = request.response_data.to_replicates();
+
+    let oa = request_to_oa(&request, &response_data)?;
+    let config = AnalysisConfig {
+        enable_pooling: false,
+        ..analysis_config(&request)
+    };
+
+    let lib_result = doe::analyze(&oa, &response_data, &config)
+        .map_err(|e| format!("Analysis failed: {}", e))?;
+    let anova = map_anova_result(&lib_result.anova, &request.factor_ids, &request.factor_names)?;
+
+    let mut entries = anova.entries;
+    entries.sort_by(|a, b| {
+        a.contribution_percent
+            .partial_cmp(&b.contribution_percent)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let min_unpooled = request.min_unpooled_factors.unwrap_or(1);
+    let max_pooled = entries.len().saturating_sub(min_unpooled);
+
+    let mut error_df = anova.error_df;
+    let mut pooling = true;
+    let suggestions = entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            if error_df * 2 >= anova.total_df || i >= max_pooled {
+                pooling = false;
+            }
+            error_df += entry.degrees_of_freedom;
+            PoolingSuggestion {
+                factor_id: entry.factor_id,
+                factor_name: entry.factor_name,
+                contribution_percent: entry.contribution_percent,
+                degrees_of_freedom: entry.degrees_of_freedom,
+                pool: pooling,
+                error_df,
+            }
+        })
+        .collect();
+
+    Ok(suggestions)
+}
+
+/// Get the mean response at every level combination of two factors.
+#[tauri::command]
+pub fn get_interaction_plot_data(
+    request: DOEAnalysisRequest,
+    factor_a: String,
+    factor_b: String,
+) -> Result<InteractionPlotData, String> {
+    let response_data = request.response_data.to_replicates();
+    let oa = request_to_oa(&request, &response_data)?;
+
+    let col_a = factor_column(&request, &factor_a)?;
+    let col_b = factor_column(&request, &factor_b)?;
+    if col_a == col_b {
+        return Err("Interaction requires two different factors".to_string());
+    }
+
+    let levels_a = oa.column(col_a).iter().copied().max().unwrap_or(0) as usize + 1;
+    let levels_b = oa.column(col_b).iter().copied().max().unwrap_or(0) as usize + 1;
+
+    // Accumulate run averages per (level_a, level_b) cell
+    let mut sums = vec![vec![0.0; levels_b]; levels_a];
+    let mut counts = vec![vec![0usize; levels_b]; levels_a];
+    for (run, average) in run_averages(&response_data).into_iter().enumerate() {
+        let a = oa.get(run, col_a) as usize;
+        let b = oa.get(run, col_b) as usize;
+        sums[a][b] += average;
+        counts[a][b] += 1;
+    }
+
+    let means = sums
+        .iter()
+        .zip(counts.iter())
+        .map(|(row_sums, row_counts)| {
+            row_sums
+                .iter()
+                .zip(row_counts.iter())
+                .map(|(&sum, &count)| if count > 0 { Some(sum / count as f64) } else { None })
+                .collect()
+        })
+        .collect();
+
+    Ok(InteractionPlotData {
+        factor_a_id: factor_a,
+        factor_a_name: request.factor_names[col_a].clone(),
+        factor_b_id: factor_b,
+        factor_b_name: request.factor_names[col_b].clone(),
+        means,
+        counts,
+    })
+}
+
+/// Get the classic Taguchi response table (mean response per factor level).
+///
+/// Skips S/N ratios and ANOVA for a fast first look at the data.
+#[tauri::command]
+pub fn get_response_table(request: DOEAnalysisRequest) -> Result<ResponseTable, String> {
+    let response_data = request.response_data.to_replicates();
+    let oa = request_to_oa(&request, &response_data)?;
+
+    let averages = run_averages(&response_data);
+    let grand_mean = averages.iter().sum::<f64>() / averages.len() as f64;
+
+    let factors = (0..oa.factors())
+        .map(|col| ResponseTableRow {
+            factor_id: request.factor_ids[col].clone(),
+            factor_name: request.factor_names[col].clone(),
+            level_means: level_means(&oa.column(col).to_vec(), &averages, grand_mean),
+        })
+        .collect();
+
+    Ok(ResponseTable { grand_mean, factors })
+}
+
+/// Each factor's main-effect range as a percentage of the sum of all ranges.
+///
+/// Based on the response table, so it needs no error term and works for
+/// designs without replicates. Unlike ANOVA contribution, it is not a share
+/// of the total variation.
+#[tauri::command]
+pub fn get_factor_importance(request: DOEAnalysisRequest) -> Result<Vec<FactorImportance>, String> {
+    let table = get_response_table(request)?;
+
+    let ranges: Vec<f64> = table
+        .factors
+        .iter()
+        .map(|row| {
+            let max = row.level_means.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let min = row.level_means.iter().copied().fold(f64::INFINITY, f64::min);
+            if max >= min { max - min } else { 0.0 }
+        })
+        .collect();
+    let total: f64 = ranges.iter().sum();
+
+    Ok(table
+        .factors
+        .into_iter()
+        .zip(ranges)
+        .map(|(row, range)| FactorImportance {
+            factor_id: row.factor_id,
+            factor_name: row.factor_name,
+            range,
+            percent: if total > 0.0 { range / total * 100.0 } else { 0.0 },
+        })
+        .collect())
+}
+
+/// Split one factor's main effect into linear, quadratic, ... components.
+///
+/// Coefficients are orthogonal polynomials over the level indices, weighted by
+/// the runs at each level, so the component sums of squares add up to the
+/// factor's ANOVA SS. Each component is F-tested against the error term of
+/// `run_doe_analysis`. This assumes equally spaced levels; pass the actual
+/// `level_values` to be warned when they are not.
+#[tauri::command]
+pub fn get_polynomial_contrasts(
+    request: DOEAnalysisRequest,
+    factor_col: usize,
+    level_values: Option<Vec<f64>>,
+) -> Result<PolynomialContrasts, String> {
+    let response_data = request.response_data.to_replicates();
+    let oa = request_to_oa(&request, &response_data)?;
+    if factor_col >= oa.factors() {
+        return Err(format!(
+            "Factor column {} is out of range (array has {} factors)",
+            factor_col,
+            oa.factors()
+        ));
+    }
+
+    let config = analysis_config(&request);
+    let lib_result = doe::analyze(&oa, &response_data, &config)
+        .map_err(|e| format!("Analysis failed: {}", e))?;
+    check_anova(&lib_result.anova)?;
+    let error_df = lib_result.anova.error_df;
+    let error_ms = lib_result.anova.error_ms;
+
+    let column = oa.column(factor_col).to_vec();
+    let averages = run_averages(&response_data);
+    let grand_mean = averages.iter().sum::<f64>() / averages.len() as f64;
+    let means = level_means(&column, &averages, grand_mean);
+    let num_levels = means.len();
+
+    let mut counts = vec![0usize; num_levels];
+    for &level in &column {
+        counts[level as usize] += 1;
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(values) = &level_values {
+        if values.len() != num_levels {
+            return Err(format!(
+                "Expected {} level values, got {}",
+                num_levels,
+                values.len()
+            ));
+        }
+        if !is_equally_spaced(values) {
+            warnings.push(
+                "Level values are not equally spaced; the components are only approximate"
+                    .to_string(),
+            );
+        }
+    }
+    if counts.iter().any(|&n| n != counts[0]) {
+        warnings.push(
+            "Levels have unequal run counts; coefficients are weighted by run count".to_string(),
+        );
+    }
+    if error_df == 0 {
+        warnings.push(zero_error_df_message("significance"));
+    }
+
+    let components = orthogonal_polynomials(&counts)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, coefficients)| {
+            let weighted: f64 = (0..num_levels)
+                .map(|j| counts[j] as f64 * coefficients[j] * means[j])
+                .sum();
+            let norm: f64 = (0..num_levels)
+                .map(|j| counts[j] as f64 * coefficients[j].powi(2))
+                .sum();
+            let sum_of_squares = weighted.powi(2) / norm;
+
+            let f_ratio = if error_ms > 0.0 && error_df > 0 {
+                Some(sum_of_squares / error_ms)
+            } else {
+                None
+            };
+            let p_value = f_ratio.map(|f| doe::f_distribution_p_value(f, 1, error_df));
+
+            let degree = idx + 1;
+            PolynomialComponent {
+                degree,
+                name: polynomial_name(degree),
+                contrast: coefficients.iter().zip(&means).map(|(c, m)| c * m).sum(),
+                coefficients,
+                sum_of_squares,
+                f_ratio,
+                p_value,
+            }
+        })
+        .collect();
+
+    Ok(PolynomialContrasts {
+        factor_id: request.factor_ids[factor_col].clone(),
+        factor_name: request.factor_names[factor_col].clone(),
+        components,
+        error_df,
+        warnings,
+    })
+}
+
+/// Mean response at each level of one factor.
+///
+/// A lightweight alternative to the full analysis for interactive exploration.
+/// Levels with no runs fall back to the grand mean.
+#[tauri::command]
+pub fn factor_marginal_means(
+    array_data: Vec<Vec<u32>>,
+    response_data: Vec<Vec<f64>>,
+    factor_col: usize,
+) -> Result<Vec<f64>, String> {
+    if array_data.is_empty() {
+        return Err("Array data is empty".to_string());
+    }
+    if array_data.len() != response_data.len() {
+        return Err("Array data and response data must have same number of runs".to_string());
+    }
+    if let Some(run) = array_data.iter().position(|row| factor_col >= row.len()) {
+        return Err(format!(
+            "Factor column {} is out of range (run {} has {} columns)",
+            factor_col,
+            run + 1,
+            array_data[run].len()
+        ));
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.is_empty()) {
+        return Err(format!("Response data for run {} is empty", run + 1));
+    }
+
+    let averages = run_averages(&response_data);
+    let grand_mean = averages.iter().sum::<f64>() / averages.len() as f64;
+    let column: Vec<u32> = array_data.iter().map(|row| row[factor_col]).collect();
+
+    Ok(level_means(&column, &averages, grand_mean))
+}
+
+/// Standard error of each factor's main effect.
+///
+/// Uses the error mean square from the same ANOVA as `run_doe_analysis`
+/// (including pooling). A level mean over `n` runs of `r` replicates has
+/// standard error `sqrt(MSe / (n * r))`.
+#[tauri::command]
+pub fn get_effect_standard_errors(request: DOEAnalysisRequest) -> Result<Vec<FactorSE>, String> {
+    let response_data = request.response_data.to_replicates();
+    let oa = request_to_oa(&request, &response_data)?;
+    let config = analysis_config(&request);
+
+    let lib_result = doe::analyze(&oa, &response_data, &config)
+        .map_err(|e| format!("Analysis failed: {}", e))?;
+    check_anova(&lib_result.anova)?;
+
+    let error_df = lib_result.anova.error_df;
+    let error_ms = lib_result.anova.error_ms;
+    if error_df == 0 {
+        return Err(zero_error_df_message("standard errors"));
+    }
+
+    // The library takes the replicate count from the first run
+    let replicates = response_data.first().map_or(1, |reps| reps.len()) as f64;
+
+    Ok((0..oa.factors())
+        .map(|col| {
+            let column = oa.column(col).to_vec();
+            let num_levels = column.iter().copied().max().unwrap_or(0) as usize + 1;
+            let mut counts = vec![0usize; num_levels];
+            for &level in &column {
+                counts[level as usize] += 1;
+            }
+
+            let level_standard_errors = counts
+                .iter()
+                .map(|&n| {
+                    if n > 0 {
+                        (error_ms / (n as f64 * replicates)).sqrt()
+                    } else {
+                        f64::NAN
+                    }
+                })
+                .collect();
+            let present_levels = counts.iter().filter(|&&n| n > 0).count();
+            let runs_per_level = oa.runs() as f64 / present_levels as f64;
+
+            FactorSE {
+                factor_id: request.factor_ids[col].clone(),
+                factor_name: request.factor_names[col].clone(),
+                level_standard_errors,
+                effect_standard_error: (2.0 * error_ms / (runs_per_level * replicates)).sqrt(),
+                error_df,
+            }
+        })
+        .collect())
+}
+
+/// Level means of every factor plus one decision band, grand mean ± LSD.
+///
+/// `LSD = t · sqrt(2 · MSe / (n · r))` at the request's confidence level, using
+/// the ANOVA error term of `run_doe_analysis`. `n` is the fewest runs at any
+/// level of any factor, so the band is conservative for mixed-level arrays.
+/// Levels whose means fall outside the band are flagged as significant.
+#[tauri::command]
+pub fn get_effects_plot_bands(request: DOEAnalysisRequest) -> Result<EffectsPlotBands, String> {
+    let response_data = request.response_data.to_replicates();
+    let oa = request_to_oa(&request, &response_data)?;
+    let config = analysis_config(&request);
+
+    let lib_result = doe::analyze(&oa, &response_data, &config)
+        .map_err(|e| format!("Analysis failed: {}", e))?;
+    check_anova(&lib_result.anova)?;
+
+    let error_df = lib_result.anova.error_df;
+    if error_df == 0 {
+        return Err(zero_error_df_message("decision band"));
+    }
+
+    let averages = run_averages(&response_data);
+    let grand_mean = averages.iter().sum::<f64>() / averages.len() as f64;
+    let columns: Vec<Vec<u32>> = (0..oa.factors()).map(|col| oa.column(col).to_vec()).collect();
+
+    // Fewest runs behind any plotted level mean
+    let min_runs = columns
+        .iter()
+        .flat_map(|column| {
+            let mut counts: HashMap<u32, usize> = HashMap::new();
+            for &level in column {
+                *counts.entry(level).or_insert(0) += 1;
+            }
+            counts.into_values()
+        })
+        .min()
+        .unwrap_or(1);
+
+    // The library takes the replicate count from the first run
+    let replicates = response_data.first().map_or(1, |reps| reps.len());
+    let confidence_level = config.confidence_level;
+    let t = doe::t_value(confidence_level, error_df);
+    let lsd = t * (2.0 * lib_result.anova.error_ms / (min_runs * replicates) as f64).sqrt();
+
+    let factors = columns
+        .iter()
+        .enumerate()
+        .map(|(col, column)| {
+            let level_means = level_means(column, &averages, grand_mean);
+            let significant_levels = level_means
+                .iter()
+                .enumerate()
+                .filter(|(_, &mean)| (mean - grand_mean).abs() > lsd)
+                .map(|(level, _)| level)
+                .collect();
+            EffectsPlotFactor {
+                factor_id: request.factor_ids[col].clone(),
+                factor_name: request.factor_names[col].clone(),
+                level_means,
+                significant_levels,
+            }
+        })
+        .collect();
+
+    Ok(EffectsPlotBands {
+        grand_mean,
+        lsd,
+        lower: grand_mean - lsd,
+        upper: grand_mean + lsd,
+        confidence_level,
+        factors,
+    })
+}
+
+/// Check array and response data for structural problems before analysis.
+///
+/// Runs the same shape and level checks `run_doe_analysis` applies when it
+/// builds the OA, and also reports balance, so problems surface up front
+/// rather than as a failed OA construction.
+#[tauri::command]
+pub fn precheck_for_analysis(
+    array_data: Vec<Vec<u32>>,
+    response_data: Vec<Vec<f64>>,
+) -> PrecheckReport {
+    check_design_data(&array_data, &response_data, &[])
+}
+
+/// Check a DOE setup without running the analysis.
+///
+/// Reports errors that would make `run_doe_analysis` fail and warnings about
+/// balance, error degrees of freedom, and replication.
+#[tauri::command]
+pub fn validate_doe_setup(request: DOEAnalysisRequest) -> DoeValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let response_data = request.response_data.to_replicates();
+
+    let checks = check_design_data(&request.array_data, &response_data, &request.factor_names);
+    errors.extend(checks.errors);
+    warnings.extend(checks.warnings);
+
+    // Building the OA repeats the shape and level checks, so its error only adds
+    // something new once those have passed
+    let oa = match request_to_oa(&request, &response_data) {
+        Ok(oa) => Some(oa),
+        Err(e) => {
+            if checks.ready {
+                errors.push(e);
+            }
+            None
+        }
+    };
+    match validate_target(&request) {
+        Ok(w) => warnings.extend(w),
+        Err(e) => errors.push(e),
+    }
+    // Non-finite values are already reported by the shared checks
+    if checks.responses_aligned {
+        match validate_response_values(&response_data, &request.optimization_type) {
+            Ok(w) => warnings.extend(w),
+            Err(e) => errors.push(e),
+        }
+    }
+    if let Err(e) = validate_sort_by(&request) {
+        errors.push(e);
+    }
+    if let Err(e) = validate_sn_formula(&request, &response_data) {
+        errors.push(e);
+    }
+
+    if let Some(oa) = &oa {
+        let factor_df: usize = checks.levels.iter().map(|&l| l.saturating_sub(1) as usize).sum();
+
+        // Error degrees of freedom for ANOVA
+        let replicates = response_data.iter().map(|reps| reps.len()).min().unwrap_or(0);
+        if response_data.iter().any(|reps| reps.len() != replicates) {
+            warnings.push("Runs have different numbers of replicates".to_string());
+        }
+        if replicates < 2 {
+            let residual_df = oa.runs().saturating_sub(1).saturating_sub(factor_df);
+            warnings.push("No replicates; ANOVA error is estimated from residuals".to_string());
+            if residual_df == 0 {
+                let pooling = request.enable_pooling.unwrap_or(true);
+                let min_unpooled = request.min_unpooled_factors.unwrap_or(1);
+                if !pooling {
+                    warnings.push(
+                        "Zero error degrees of freedom and pooling is disabled; F-tests will be unavailable"
+                            .to_string(),
+                    );
+                } else if oa.factors() <= min_unpooled {
+                    warnings.push(format!(
+                        "Zero error degrees of freedom and only {} factor(s), so none can be pooled",
+                        oa.factors()
+                    ));
+                }
+            }
+        }
+    }
+
+    DoeValidation {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
+
+/// Compute the S/N ratio over a subset of runs.
+///
+/// Averages the per-run S/N ratios of the given (0-based) runs, the same way
+/// `SNRatioEffect.level_sn_ratios` averages the runs at each level. The
+/// selected runs are checked like `run_doe_analysis` checks its input, so
+/// e.g. a zero response is rejected for larger-is-better.
+#[tauri::command]
+pub fn sn_for_subset(
+    response_data: Vec<Vec<f64>>,
+    run_indices: Vec<usize>,
+    opt_type: OptimizationType,
+    target: Option<f64>,
+) -> Result<f64, String> {
+    if run_indices.is_empty() {
+        return Err("At least one run must be selected".to_string());
+    }
+    if let Some(&run) = run_indices.iter().find(|&&r| r >= response_data.len()) {
+        return Err(format!(
+            "Run index {} is out of range (response data has {} runs)",
+            run,
+            response_data.len()
+        ));
+    }
+    if let Some(&run) = run_indices.iter().find(|&&r| response_data[r].is_empty()) {
+        return Err(format!("Response data for run {} is empty", run + 1));
+    }
+    for &run in &run_indices {
+        validate_run_responses(run, &response_data[run], &opt_type)?;
+    }
+
+    let total: f64 = run_indices
+        .iter()
+        .map(|&run| custom_sn_ratio(&response_data[run], &opt_type, target, true, false))
+        .sum();
+
+    Ok(total / run_indices.len() as f64)
+}
+
+/// Compute each run's own S/N ratio, before aggregation into factor effects.
+///
+/// A run with an anomalously low S/N compared to the rest often points to a
+/// measurement problem. Every run needs at least two replicates, and the
+/// responses are checked as in `run_doe_analysis`.
+#[tauri::command]
+pub fn get_run_sn_contributions(
+    response_data: Vec<Vec<f64>>,
+    opt_type: OptimizationType,
+    target: Option<f64>,
+) -> Result<Vec<f64>, String> {
+    if response_data.is_empty() {
+        return Err("Response data is empty".to_string());
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.len() < 2) {
+        return Err(format!(
+            "Run {} has {} replicate(s); at least 2 are needed for a meaningful S/N ratio",
+            run + 1,
+            response_data[run].len()
+        ));
+    }
+    validate_response_values(&response_data, &opt_type)?;
+
+    Ok(response_data
+        .iter()
+        .map(|reps| custom_sn_ratio(reps, &opt_type, target, true, false))
+        .collect())
+}
+
+/// Split response variance into between-run and within-run components.
+///
+/// Fits a one-way random-effects model with runs as groups. The between-run
+/// component is `(MSB - MSW) / n0`, truncated at zero, where `n0` is the
+/// effective replicates per run; the within-run component is `MSW`.
+#[tauri::command]
+pub fn variance_components(response_data: Vec<Vec<f64>>) -> Result<VarianceComponents, String> {
+    let runs = response_data.len();
+    if runs < 2 {
+        return Err("At least 2 runs are needed".to_string());
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.is_empty()) {
+        return Err(format!("Response data for run {} is empty", run + 1));
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.iter().any(|v| !v.is_finite())) {
+        return Err(format!("Response data for run {} contains a non-finite value", run + 1));
+    }
+
+    let total_obs: usize = response_data.iter().map(|reps| reps.len()).sum();
+    if total_obs <= runs {
+        return Err("At least one run needs 2 or more replicates to estimate repeatability".to_string());
+    }
+
+    let averages = run_averages(&response_data);
+    let grand_mean = response_data.iter().flatten().sum::<f64>() / total_obs as f64;
+
+    let ss_between: f64 = response_data
+        .iter()
+        .zip(&averages)
+        .map(|(reps, mean)| reps.len() as f64 * (mean - grand_mean).powi(2))
+        .sum();
+    let ss_within: f64 = response_data
+        .iter()
+        .zip(&averages)
+        .map(|(reps, mean)| reps.iter().map(|v| (v - mean).powi(2)).sum::<f64>())
+        .sum();
+
+    let ms_between = ss_between / (runs - 1) as f64;
+    let ms_within = ss_within / (total_obs - runs) as f64;
+
+    // Effective replicates per run; equals the replicate count when balanced
+    let n = total_obs as f64;
+    let sum_sq: f64 = response_data.iter().map(|reps| (reps.len() as f64).powi(2)).sum();
+    let n0 = (n - sum_sq / n) / (runs - 1) as f64;
+
+    let between_run = ((ms_between - ms_within) / n0).max(0.0);
+    let within_run = ms_within;
+    let total = between_run + within_run;
+    let percent = |component: f64| if total > 0.0 { component / total * 100.0 } else { 0.0 };
+
+    Ok(VarianceComponents {
+        between_run,
+        within_run,
+        total,
+        between_run_percent: percent(between_run),
+        within_run_percent: percent(within_run),
+    })
+}
+
+/// Test for curvature by comparing center runs with the factorial runs.
+///
+/// For a two-level design augmented with center runs, the curvature sum of
+/// squares is `nF·nC·(ȳF - ȳC)² / (nF + nC)` on one degree of freedom, tested
+/// against the pure error from the spread of the center-run averages. Needs at
+/// least two center runs, and the factorial runs must not use the middle level.
+#[tauri::command]
+pub fn curvature_test(
+    array_data: Vec<Vec<u32>>,
+    response_data: Vec<Vec<f64>>,
+) -> Result<CurvatureTest, String> {
+    check_array_shape(&array_data)?;
+    if response_data.len() != array_data.len() {
+        return Err(format!(
+            "Response data has {} runs but the array has {}",
+            response_data.len(),
+            array_data.len()
+        ));
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.is_empty()) {
+        return Err(format!("Response data for run {} is empty", run + 1));
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.iter().any(|v| !v.is_finite())) {
+        return Err(format!("Response data for run {} contains a non-finite value", run + 1));
+    }
+
+    let levels = analysis_levels(&array_data)?;
+    let center_rows = find_center_points(&array_data, &levels);
+    if center_rows.len() < 2 {
+        return Err("At least 2 center runs are needed to estimate pure error".to_string());
+    }
+    let middle = |col: usize, v: u32| levels[col] % 2 == 1 && v == (levels[col] - 1) / 2;
+    let factorial: Vec<usize> = (0..array_data.len()).filter(|r| !center_rows.contains(r)).collect();
+    if factorial.is_empty() {
+        return Err("The design has no factorial runs".to_string());
+    }
+    if let Some(&run) = factorial
+        .iter()
+        .find(|&&r| array_data[r].iter().enumerate().any(|(col, &v)| middle(col, v)))
+    {
+        return Err(format!(
+            "Run {} uses a middle level; curvature is only tested for two-level designs with center runs",
+            run + 1
+        ));
+    }
+
+    let averages = run_averages(&response_data);
+    let mean_of = |rows: &[usize]| rows.iter().map(|&r| averages[r]).sum::<f64>() / rows.len() as f64;
+    let factorial_mean = mean_of(&factorial);
+    let center_mean = mean_of(&center_rows);
+
+    let n_f = factorial.len() as f64;
+    let n_c = center_rows.len() as f64;
+    let sum_of_squares = n_f * n_c * (factorial_mean - center_mean).powi(2) / (n_f + n_c);
+    let error_df = center_rows.len() - 1;
+    let error_mean_square = center_rows
+        .iter()
+        .map(|&r| (averages[r] - center_mean).powi(2))
+        .sum::<f64>()
+        / error_df as f64;
+    if error_mean_square <= 0.0 {
+        return Err("The center runs have identical responses; pure error is zero".to_string());
+    }
+
+    let f_value = sum_of_squares / error_mean_square;
+    Ok(CurvatureTest {
+        center_rows,
+        factorial_runs: factorial.len(),
+        factorial_mean,
+        center_mean,
+        sum_of_squares,
+        error_mean_square,
+        error_df,
+        f_value,
+        p_value: f_upper_tail(f_value, 1, error_df),
+    })
+}
+
+/// Compute main effects directly from the data, without requiring a valid OA.
+///
+/// Works for unbalanced or non-orthogonal designs where `run_doe_analysis`
+/// fails to construct an OA. Factors are labelled by column ("Factor 1", ...),
+/// and nominal-is-best levels are scored by distance to the grand mean.
+#[tauri::command]
+pub fn compute_main_effects_raw(
+    array_data: Vec<Vec<u32>>,
+    response_data: Vec<Vec<f64>>,
+    opt_type: OptimizationType,
+) -> Result<Vec<MainEffect>, String> {
+    if array_data.is_empty() {
+        return Err("Array data is empty".to_string());
+    }
+    if array_data.len() != response_data.len() {
+        return Err("Array data and response data must have same number of runs".to_string());
+    }
+    let factors = array_data[0].len();
+    if let Some(run) = array_data.iter().position(|row| row.len() != factors) {
+        return Err(format!(
+            "Row {} has {} columns, expected {}",
+            run + 1,
+            array_data[run].len(),
+            factors
+        ));
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.is_empty()) {
+        return Err(format!("Response data for run {} is empty", run + 1));
+    }
+
+    let averages = run_averages(&response_data);
+    let grand_mean = averages.iter().sum::<f64>() / averages.len() as f64;
+
+    let mut effects: Vec<MainEffect> = (0..factors)
+        .map(|col| {
+            let column: Vec<u32> = array_data.iter().map(|row| row[col]).collect();
+            let means = level_means(&column, &averages, grand_mean);
+            let max = means.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let min = means.iter().copied().fold(f64::INFINITY, f64::min);
+            let (best_level, worst_level) = best_worst_levels(&means, &opt_type, grand_mean);
+
+            MainEffect {
+                factor_id: col.to_string(),
+                factor_name: format!("Factor {}", col + 1),
+                level_effects: means.iter().map(|m| m - grand_mean).collect(),
+                level_means: means,
+                range: max - min,
+                rank: 0, // Set below
+                best_level,
+                worst_level,
+            }
+        })
+        .collect();
+
+    // Rank by range, largest first
+    let mut order: Vec<usize> = (0..effects.len()).collect();
+    order.sort_by(|&a, &b| effects[b].range.total_cmp(&effects[a].range));
+    for (rank, idx) in order.into_iter().enumerate() {
+        effects[idx].rank = rank + 1;
+    }
+
+    Ok(effects)
+}
+
+/// Validate the request and build the library OA from its array data
+fn request_to_oa(request: &DOEAnalysisRequest, response_data: &[Vec<f64>]) -> Result<OA, String> {
     // Validate inputs
     if request.array_data.is_empty() {
         return Err("Array data is empty".to_string());
     }
-    if request.response_data.is_empty() {
+    if response_data.is_empty() {
         return Err("Response data is empty".to_string());
     }
-    if request.array_data.len() != request.response_data.len() {
+    if request.array_data.len() != response_data.len() {
         return Err("Array data and response data must have same number of runs".to_string());
     }
 
+    check_array_shape(&request.array_data)?;
+
     let num_runs = request.array_data.len();
     let num_factors = request.array_data[0].len();
 
@@ -39,14 +919,7 @@ pub fn run_doe_analysis(request: DOEAnalysisRequest) -> Result<DOEAnalysis, Stri
     }
 
     // Determine levels per factor from the array data
-    let levels_per_factor: Vec<u32> = (0..num_factors)
-        .map(|col| {
-            let mut levels: Vec<u32> = request.array_data.iter().map(|row| row[col]).collect();
-            levels.sort();
-            levels.dedup();
-            levels.len() as u32
-        })
-        .collect();
+    let levels_per_factor = analysis_levels(&request.array_data)?;
 
     // Convert Vec<Vec<u32>> to Array2<u32>
     let array_2d = convert_to_array2(&request.array_data)
@@ -55,9 +928,248 @@ pub fn run_doe_analysis(request: DOEAnalysisRequest) -> Result<DOEAnalysis, Stri
     // Create OA params and OA
     let params = OAParams::new_mixed(num_runs, levels_per_factor, 2)
         .map_err(|e| format!("Invalid OA parameters: {}", e))?;
-    let oa = OA::try_new(array_2d, params)
-        .map_err(|e| format!("Failed to create OA: {}", e))?;
+    OA::try_new(array_2d, params).map_err(|e| format!("Failed to create OA: {}", e))
+}
+
+/// Shape, level, response and balance checks shared by the analysis prechecks.
+///
+/// Factors are named from `factor_names` where given, and numbered otherwise.
+fn check_design_data(
+    array_data: &[Vec<u32>],
+    response_data: &[Vec<f64>],
+    factor_names: &[String],
+) -> PrecheckReport {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let factor = |col: usize| match factor_names.get(col) {
+        Some(name) => format!("Factor '{}'", name),
+        None => format!("Factor {}", col + 1),
+    };
+
+    let shape_consistent = match check_array_shape(array_data) {
+        Ok(()) => true,
+        Err(e) => {
+            errors.push(e);
+            false
+        }
+    };
+
+    let levels = if shape_consistent {
+        match analysis_levels(array_data) {
+            Ok(levels) => levels,
+            Err(e) => {
+                errors.push(e);
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    for (col, &l) in levels.iter().enumerate() {
+        if l < 2 {
+            errors.push(format!(
+                "{} has only one level; its effect cannot be estimated",
+                factor(col)
+            ));
+        }
+    }
+    // The analysis builds its OA with strength 2
+    if levels.len() == 1 {
+        errors.push("At least two factors are required for analysis".to_string());
+    }
+
+    let mut responses_aligned = true;
+    if response_data.len() != array_data.len() {
+        errors.push(format!(
+            "Response data has {} runs, but the array has {}",
+            response_data.len(),
+            array_data.len()
+        ));
+        responses_aligned = false;
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.is_empty()) {
+        errors.push(format!("Response data for run {} is empty", run + 1));
+        responses_aligned = false;
+    }
+    if let Some(run) = response_data.iter().position(|reps| reps.iter().any(|v| !v.is_finite())) {
+        errors.push(format!("Response data for run {} contains a non-finite value", run + 1));
+        responses_aligned = false;
+    }
+
+    let mut balanced = shape_consistent && !levels.is_empty();
+    if balanced {
+        for col in 0..levels.len() {
+            let mut counts: HashMap<u32, usize> = HashMap::new();
+            for row in array_data {
+                *counts.entry(row[col]).or_insert(0) += 1;
+            }
+            if counts.values().min() != counts.values().max() {
+                warnings.push(format!("{} is unbalanced; main effects may be biased", factor(col)));
+                balanced = false;
+            }
+        }
+    }
+
+    PrecheckReport {
+        ready: errors.is_empty(),
+        shape_consistent,
+        responses_aligned,
+        balanced,
+        levels,
+        errors,
+        warnings,
+    }
+}
+
+/// Check the array is non-empty and rectangular
+fn check_array_shape(array_data: &[Vec<u32>]) -> Result<(), String> {
+    let num_factors = match array_data.first() {
+        Some(row) => row.len(),
+        None => return Err("Array data is empty".to_string()),
+    };
+    if num_factors == 0 {
+        return Err("Array must have at least one factor".to_string());
+    }
+    if let Some(run) = array_data.iter().position(|row| row.len() != num_factors) {
+        return Err(format!(
+            "Row {} has {} columns, expected {}",
+            run + 1,
+            array_data[run].len(),
+            num_factors
+        ));
+    }
+    Ok(())
+}
+
+/// Count the levels of each factor, requiring them to be numbered 0..levels-1
+fn analysis_levels(array_data: &[Vec<u32>]) -> Result<Vec<u32>, String> {
+    let num_factors = array_data.first().map_or(0, |row| row.len());
+    (0..num_factors)
+        .map(|col| {
+            let mut levels: Vec<u32> = array_data.iter().map(|row| row[col]).collect();
+            levels.sort();
+            levels.dedup();
+            if levels.iter().enumerate().any(|(i, &level)| level != i as u32) {
+                return Err(format!(
+                    "Factor {} uses levels {:?}; levels must be numbered 0 to {} without gaps",
+                    col + 1,
+                    levels,
+                    levels.len() - 1
+                ));
+            }
+            Ok(levels.len() as u32)
+        })
+        .collect()
+}
+
+/// Check that response values are safe for the S/N formula
+///
+/// Larger-is-better uses 1/y², so non-positive responses are rejected. Other
+/// modes accept negative responses but return a warning for them.
+fn validate_response_values(
+    response_data: &[Vec<f64>],
+    optimization_type: &OptimizationType,
+) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+
+    for (run, reps) in response_data.iter().enumerate() {
+        warnings.extend(validate_run_responses(run, reps, optimization_type)?);
+    }
+
+    Ok(warnings)
+}
+
+/// Check one run's (0-based) replicates, returning a warning if any is suspect
+fn validate_run_responses(
+    run: usize,
+    reps: &[f64],
+    optimization_type: &OptimizationType,
+) -> Result<Option<String>, String> {
+    if let Some(value) = reps.iter().find(|v| !v.is_finite()) {
+        return Err(format!("Response values must be finite; run {} has {}", run + 1, value));
+    }
+
+    match optimization_type {
+        OptimizationType::LargerIsBetter => {
+            if let Some(value) = reps.iter().find(|&&v| v <= 0.0) {
+                return Err(format!(
+                    "Larger-is-better S/N requires positive responses; run {} has {:?}",
+                    run + 1,
+                    value
+                ));
+            }
+            Ok(None)
+        }
+        _ => Ok(reps.iter().find(|&&v| v < 0.0).map(|value| {
+            format!(
+                "Run {} has negative response {:?}; S/N ratios may be misleading",
+                run + 1,
+                value
+            )
+        })),
+    }
+}
+
+/// Check that a target value is given exactly when the optimization type uses one
+fn validate_target(request: &DOEAnalysisRequest) -> Result<Vec<String>, String> {
+    match (&request.optimization_type, request.target_value) {
+        (OptimizationType::NominalIsBest, None) => {
+            Err("NominalIsBest requires target_value".to_string())
+        }
+        (OptimizationType::NominalIsBest, Some(_)) => Ok(Vec::new()),
+        (_, Some(target)) => Ok(vec![format!(
+            "Target value {} is ignored for larger-is-better and smaller-is-better analysis",
+            target
+        )]),
+        (_, None) => Ok(Vec::new()),
+    }
+}
 
+/// Check the requested ANOVA order, returning it with the default applied
+fn validate_sort_by(request: &DOEAnalysisRequest) -> Result<&str, String> {
+    let sort_by = request.sort_by.as_deref().unwrap_or("factor");
+    if matches!(sort_by, "factor" | "contribution" | "f_ratio") {
+        Ok(sort_by)
+    } else {
+        Err(format!(
+            "Unknown sort order '{}' (expected \"factor\", \"contribution\", or \"f_ratio\")",
+            sort_by
+        ))
+    }
+}
+
+/// Check a custom S/N formula can be applied to the request
+///
+/// The variance noise term ignores the mean, so it only makes sense for
+/// nominal-is-best, and needs at least two replicates per run to exist.
+fn validate_sn_formula(
+    request: &DOEAnalysisRequest,
+    response_data: &[Vec<f64>],
+) -> Result<(), String> {
+    let use_variance =
+        matches!(request.sn_formula, Some(SNFormula::Custom { use_variance: true, .. }));
+    if !use_variance {
+        return Ok(());
+    }
+
+    if request.optimization_type != OptimizationType::NominalIsBest {
+        return Err(
+            "The variance S/N formula is only defined for nominal-is-best; larger- and smaller-is-better need the mean in the noise term"
+                .to_string(),
+        );
+    }
+    match response_data.iter().position(|reps| reps.len() < 2) {
+        Some(run) => Err(format!(
+            "The variance S/N formula needs at least 2 replicates per run; run {} has {}",
+            run + 1,
+            response_data[run].len()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Build the library analysis config from request settings with defaults
+fn analysis_config(request: &DOEAnalysisRequest) -> AnalysisConfig {
     // Convert optimization type
     let lib_opt_type = match request.optimization_type {
         OptimizationType::LargerIsBetter => LibOptType::LargerIsBetter,
@@ -66,35 +1178,379 @@ pub fn run_doe_analysis(request: DOEAnalysisRequest) -> Result<DOEAnalysis, Stri
     };
 
     // Configure analysis using request settings with defaults
-    let config = AnalysisConfig {
+    AnalysisConfig {
         optimization_type: lib_opt_type,
         target_value: request.target_value,
         pooling_threshold: request.pooling_threshold.unwrap_or(2.0),
         enable_pooling: request.enable_pooling.unwrap_or(true),
         min_unpooled_factors: request.min_unpooled_factors.unwrap_or(1),
         confidence_level: request.confidence_level.unwrap_or(0.95),
+    }
+}
+
+/// SHA-256 over the analysis inputs, with defaults resolved.
+///
+/// Responses are hashed by their exact bit patterns, and nested factor groups
+/// in sorted order, so the same inputs always give the same hash.
+fn input_hash(
+    request: &DOEAnalysisRequest,
+    response_data: &[Vec<f64>],
+    config: &AnalysisConfig,
+    sort_by: &str,
+) -> String {
+    let mut hasher = Sha256::new();
+    let mut line = |text: String| {
+        hasher.update(text.as_bytes());
+        hasher.update(b"\n");
     };
 
-    // Run analysis using the library
-    let lib_result = doe::analyze(&oa, &request.response_data, &config)
-        .map_err(|e| format!("Analysis failed: {}", e))?;
+    for row in &request.array_data {
+        let cells: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        line(format!("array:{}", cells.join(",")));
+    }
+    for reps in response_data {
+        let cells: Vec<String> = reps.iter().map(|v| format!("{:016x}", v.to_bits())).collect();
+        line(format!("response:{}", cells.join(",")));
+    }
 
-    // Map library results to UI types
-    let main_effects = map_main_effects(&lib_result.main_effects, &request.factor_ids, &request.factor_names);
-    let sn_ratio_effects = map_sn_ratio_effects(&lib_result.sn_ratio_effects, &request.factor_ids, &request.factor_names);
-    let anova = map_anova_result(&lib_result.anova, &request.factor_ids, &request.factor_names);
-    let optimal_settings = map_optimal_settings(&lib_result.optimal_settings, &request.factor_ids);
+    line(format!("optimization:{:?}", request.optimization_type));
+    line(format!("target:{:?}", config.target_value.map(f64::to_bits)));
+    line(format!("pooling_threshold:{:016x}", config.pooling_threshold.to_bits()));
+    line(format!("enable_pooling:{}", config.enable_pooling));
+    line(format!("min_unpooled_factors:{}", config.min_unpooled_factors));
+    line(format!("confidence_level:{:016x}", config.confidence_level.to_bits()));
+    line(format!("sn_formula:{:?}", request.sn_formula.clone().unwrap_or_default()));
+    if let Some(groups) = &request.factor_groups {
+        let mut parents: Vec<&String> = groups.keys().collect();
+        parents.sort();
+        for parent in parents {
+            line(format!("group:{}:{}", parent, groups[parent].join(",")));
+        }
+    }
+    line(format!("sort_by:{}", sort_by));
 
-    Ok(DOEAnalysis {
-        config_id: String::new(), // Will be set by frontend
-        grand_mean: lib_result.grand_mean,
-        sn_grand_mean: lib_result.sn_grand_mean,
-        main_effects,
-        sn_ratio_effects,
-        anova,
-        optimal_settings,
-        analyzed_at: chrono::Utc::now().to_rfc3339(),
-    })
+    format!("{:x}", hasher.finalize())
+}
+
+/// Explain that `quantity` cannot be computed without error degrees of freedom
+fn zero_error_df_message(quantity: &str) -> String {
+    format!(
+        "Zero error degrees of freedom; {} unavailable. Consider pooling or replicates.",
+        quantity
+    )
+}
+
+/// Describe the S/N formula applied for the request's optimization type
+///
+/// Requests must already have passed `validate_target` and
+/// `validate_sn_formula`, so nominal-is-best carries a target value and only
+/// nominal-is-best uses the variance.
+fn sn_formula_description(request: &DOEAnalysisRequest) -> String {
+    let (db_scale, use_variance) = match request.sn_formula {
+        Some(SNFormula::Custom { db_scale, use_variance }) => (db_scale, use_variance),
+        _ => (true, false),
+    };
+    let scale = if db_scale { 10 } else { 20 };
+
+    match (&request.optimization_type, use_variance) {
+        (OptimizationType::NominalIsBest, true) => format!("nominal-is-best: {}·log(ȳ²/s²)", scale),
+        (OptimizationType::NominalIsBest, false) => format!(
+            "nominal-is-best: {}·log(ȳ²/mean((y-{})²))",
+            scale,
+            request.target_value.unwrap_or_default()
+        ),
+        (OptimizationType::LargerIsBetter, _) => {
+            format!("larger-is-better: -{}·log(mean(1/y²))", scale)
+        }
+        (OptimizationType::SmallerIsBetter, _) => {
+            format!("smaller-is-better: -{}·log(mean(y²))", scale)
+        }
+    }
+}
+
+/// Calculate a run's S/N ratio using a custom variant of the standard formulas
+///
+/// The noise term is the mean squared deviation for the optimization type, or
+/// for nominal-is-best the sample variance when `use_variance` is set (see
+/// `validate_sn_formula`). Results are clamped to ±100 dB like the library's
+/// standard formulas.
+fn custom_sn_ratio(
+    values: &[f64],
+    optimization_type: &OptimizationType,
+    target_value: Option<f64>,
+    db_scale: bool,
+    use_variance: bool,
+) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let scale = if db_scale { 10.0 } else { 20.0 };
+    let sample_variance = || {
+        let denom = if values.len() > 1 { n - 1.0 } else { n };
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / denom
+    };
+
+    let result = match (optimization_type, use_variance) {
+        (OptimizationType::NominalIsBest, true) => scale * (mean * mean / sample_variance()).log10(),
+        (OptimizationType::NominalIsBest, false) => {
+            let target = target_value.unwrap_or(mean);
+            let msd = values.iter().map(|v| (v - target).powi(2)).sum::<f64>() / n;
+            scale * (mean * mean / msd).log10()
+        }
+        (OptimizationType::LargerIsBetter, _) => {
+            -scale * (values.iter().map(|v| 1.0 / (v * v)).sum::<f64>() / n).log10()
+        }
+        (OptimizationType::SmallerIsBetter, _) => {
+            -scale * (values.iter().map(|v| v * v).sum::<f64>() / n).log10()
+        }
+    };
+
+    if result.is_nan() {
+        0.0
+    } else {
+        result.clamp(-100.0, 100.0)
+    }
+}
+
+/// Replace the library's S/N effects and optimal settings with a custom formula
+fn apply_custom_sn(
+    result: &mut doe::DOEAnalysis,
+    oa: &OA,
+    response_data: &[Vec<f64>],
+    request: &DOEAnalysisRequest,
+    db_scale: bool,
+    use_variance: bool,
+) {
+    let run_sn: Vec<f64> = response_data
+        .iter()
+        .map(|reps| {
+            custom_sn_ratio(reps, &request.optimization_type, request.target_value, db_scale, use_variance)
+        })
+        .collect();
+    let sn_grand_mean = run_sn.iter().sum::<f64>() / run_sn.len().max(1) as f64;
+
+    for effect in &mut result.sn_ratio_effects {
+        effect.level_sn_ratios =
+            level_means(&oa.column(effect.factor_index).to_vec(), &run_sn, sn_grand_mean);
+        effect.optimal_level = effect
+            .level_sn_ratios
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+    }
+    result.sn_grand_mean = sn_grand_mean;
+
+    // Re-predict optimal settings with the additive model
+    let optimal = &mut result.optimal_settings;
+    let previous_mean = optimal.predicted_mean;
+    optimal.factor_levels = result.sn_ratio_effects.iter().map(|e| e.optimal_level).collect();
+    optimal.predicted_mean = result.grand_mean
+        + result
+            .main_effects
+            .iter()
+            .zip(result.sn_ratio_effects.iter())
+            .map(|(me, sn)| me.level_effects.get(sn.optimal_level).copied().unwrap_or(0.0))
+            .sum::<f64>();
+    optimal.predicted_sn_ratio = sn_grand_mean
+        + result
+            .sn_ratio_effects
+            .iter()
+            .map(sn_contribution)
+            .sum::<f64>();
+
+    // The interval half-width does not depend on the chosen levels
+    if let Some(ci) = &mut optimal.confidence_interval {
+        let shift = optimal.predicted_mean - previous_mean;
+        ci.lower += shift;
+        ci.upper += shift;
+    }
+}
+
+/// Find the array column for a factor ID
+fn factor_column(request: &DOEAnalysisRequest, factor_id: &str) -> Result<usize, String> {
+    request
+        .factor_ids
+        .iter()
+        .position(|id| id == factor_id)
+        .ok_or_else(|| format!("Unknown factor ID '{}'", factor_id))
+}
+
+/// Partition sums of squares for nested factors
+///
+/// The SS of a factor nested within a parent is the SS between the parent ×
+/// child cells minus the SS of the parent, so parent and nested SS add up to
+/// the cell SS. F-ratios use the ANOVA error term.
+fn nested_anova(
+    oa: &OA,
+    response_data: &[Vec<f64>],
+    request: &DOEAnalysisRequest,
+    groups: &HashMap<String, Vec<String>>,
+    anova: &ANOVAResult,
+) -> Result<Vec<NestedANOVAEntry>, String> {
+    let averages = run_averages(response_data);
+    let grand_mean = averages.iter().sum::<f64>() / averages.len().max(1) as f64;
+
+    // Between-cell SS and cell count for runs grouped by the given columns
+    let cell_ss = |cols: &[usize]| {
+        let mut cells: HashMap<Vec<u32>, (f64, usize)> = HashMap::new();
+        for (run, &average) in averages.iter().enumerate() {
+            let key: Vec<u32> = cols.iter().map(|&col| oa.get(run, col)).collect();
+            let cell = cells.entry(key).or_insert((0.0, 0));
+            cell.0 += average;
+            cell.1 += 1;
+        }
+        let ss: f64 = cells
+            .values()
+            .map(|&(sum, count)| count as f64 * (sum / count as f64 - grand_mean).powi(2))
+            .sum();
+        (ss, cells.len())
+    };
+
+    // Sort parents so the output order is stable
+    let mut parents: Vec<&String> = groups.keys().collect();
+    parents.sort_by_key(|id| request.factor_ids.iter().position(|f| f == *id));
+
+    let mut entries = Vec::new();
+    for parent_id in parents {
+        let parent_col = factor_column(request, parent_id)?;
+        let (parent_ss, parent_cells) = cell_ss(&[parent_col]);
+
+        for child_id in &groups[parent_id] {
+            let child_col = factor_column(request, child_id)?;
+            if child_col == parent_col {
+                return Err(format!("Factor '{}' cannot be nested within itself", child_id));
+            }
+
+            let (combined_ss, combined_cells) = cell_ss(&[parent_col, child_col]);
+            let sum_of_squares = (combined_ss - parent_ss).max(0.0);
+            let degrees_of_freedom = combined_cells - parent_cells;
+            let mean_square = if degrees_of_freedom > 0 {
+                sum_of_squares / degrees_of_freedom as f64
+            } else {
+                0.0
+            };
+
+            let f_ratio = if anova.error_ms > 0.0 && degrees_of_freedom > 0 {
+                Some(mean_square / anova.error_ms)
+            } else {
+                None
+            };
+            let p_value = f_ratio
+                .filter(|_| anova.error_df > 0)
+                .map(|f| doe::f_distribution_p_value(f, degrees_of_freedom, anova.error_df));
+
+            entries.push(NestedANOVAEntry {
+                factor_id: child_id.clone(),
+                factor_name: request.factor_names[child_col].clone(),
+                parent_id: parent_id.clone(),
+                parent_name: request.factor_names[parent_col].clone(),
+                sum_of_squares,
+                degrees_of_freedom,
+                mean_square,
+                f_ratio,
+                p_value,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Average the replicates of each run
+fn run_averages(response_data: &[Vec<f64>]) -> Vec<f64> {
+    response_data
+        .iter()
+        .map(|reps| reps.iter().sum::<f64>() / reps.len().max(1) as f64)
+        .collect()
+}
+
+/// Mean of the run averages at each level of a factor
+///
+/// Levels with no runs fall back to the grand mean, matching the library.
+fn level_means(column: &[u32], averages: &[f64], grand_mean: f64) -> Vec<f64> {
+    let num_levels = column.iter().copied().max().unwrap_or(0) as usize + 1;
+
+    let mut sums = vec![0.0; num_levels];
+    let mut counts = vec![0usize; num_levels];
+    for (&level, &average) in column.iter().zip(averages.iter()) {
+        sums[level as usize] += average;
+        counts[level as usize] += 1;
+    }
+
+    sums.iter()
+        .zip(counts.iter())
+        .map(|(&sum, &count)| if count > 0 { sum / count as f64 } else { grand_mean })
+        .collect()
+}
+
+/// Orthogonal polynomial contrast coefficients for degrees 1..levels.
+///
+/// Gram-Schmidt on the powers of the level index, with inner products weighted
+/// by the run count at each level. Each vector is scaled so its smallest
+/// non-zero coefficient is ±1, which gives the textbook tables when the counts
+/// are equal.
+fn orthogonal_polynomials(counts: &[usize]) -> Vec<Vec<f64>> {
+    let weighted_dot = |a: &[f64], b: &[f64]| -> f64 {
+        a.iter()
+            .zip(b)
+            .zip(counts)
+            .map(|((x, y), &n)| n as f64 * x * y)
+            .sum()
+    };
+
+    let mut basis: Vec<Vec<f64>> = vec![vec![1.0; counts.len()]];
+    for degree in 1..counts.len() {
+        let mut poly: Vec<f64> = (0..counts.len())
+            .map(|x| (x as f64).powi(degree as i32))
+            .collect();
+        for prev in &basis {
+            let scale = weighted_dot(&poly, prev) / weighted_dot(prev, prev);
+            for (p, q) in poly.iter_mut().zip(prev) {
+                *p -= scale * q;
+            }
+        }
+        basis.push(poly);
+    }
+
+    basis
+        .into_iter()
+        .skip(1)
+        .map(|poly| {
+            let largest = poly.iter().fold(0.0f64, |acc, c| acc.max(c.abs()));
+            let smallest = poly
+                .iter()
+                .map(|c| c.abs())
+                .filter(|&c| c > 1e-9 * largest)
+                .fold(f64::INFINITY, f64::min);
+            poly.iter().map(|c| c / smallest).collect()
+        })
+        .collect()
+}
+
+/// Display name of a polynomial degree
+fn polynomial_name(degree: usize) -> String {
+    match degree {
+        1 => "Linear".to_string(),
+        2 => "Quadratic".to_string(),
+        3 => "Cubic".to_string(),
+        4 => "Quartic".to_string(),
+        _ => format!("Degree {}", degree),
+    }
+}
+
+/// Whether consecutive values differ by the same step
+fn is_equally_spaced(values: &[f64]) -> bool {
+    if values.len() < 3 {
+        return true;
+    }
+    let step = values[1] - values[0];
+    let tolerance = 1e-9 * step.abs().max(1.0);
+    values.windows(2).all(|w| ((w[1] - w[0]) - step).abs() <= tolerance)
 }
 
 /// Convert Vec<Vec<u32>> to ndarray Array2<u32>
@@ -119,40 +1575,164 @@ fn convert_to_array2(data: &[Vec<u32>]) -> Result<Array2<u32>, String> {
         .map_err(|e| format!("Failed to create array: {}", e))
 }
 
+/// Look up the UI factor ID and name for a library factor index
+fn factor_label(
+    factor_index: usize,
+    factor_ids: &[String],
+    factor_names: &[String],
+) -> Result<(String, String), String> {
+    match (factor_ids.get(factor_index), factor_names.get(factor_index)) {
+        (Some(id), Some(name)) => Ok((id.clone(), name.clone())),
+        _ => Err(format!(
+            "Factor index {} is out of range ({} factor IDs, {} factor names)",
+            factor_index,
+            factor_ids.len(),
+            factor_names.len()
+        )),
+    }
+}
+
 /// Map library MainEffect to UI MainEffect
+///
+/// Nominal-is-best levels are scored by distance to the target value, or to
+/// the grand mean when no target is set.
 fn map_main_effects(
     effects: &[doe::MainEffect],
-    factor_ids: &[String],
-    factor_names: &[String],
-) -> Vec<MainEffect> {
+    request: &DOEAnalysisRequest,
+    grand_mean: f64,
+) -> Result<Vec<MainEffect>, String> {
     effects
         .iter()
-        .map(|e| MainEffect {
-            factor_id: factor_ids[e.factor_index].clone(),
-            factor_name: factor_names[e.factor_index].clone(),
-            level_means: e.level_means.clone(),
-            level_effects: e.level_effects.clone(),
-            range: e.range,
-            rank: e.rank,
+        .map(|e| {
+            let (factor_id, factor_name) =
+                factor_label(e.factor_index, &request.factor_ids, &request.factor_names)?;
+
+            let (best_level, worst_level) = best_worst_levels(
+                &e.level_means,
+                &request.optimization_type,
+                request.target_value.unwrap_or(grand_mean),
+            );
+
+            Ok(MainEffect {
+                factor_id,
+                factor_name,
+                level_means: e.level_means.clone(),
+                level_effects: e.level_effects.clone(),
+                range: e.range,
+                rank: e.rank,
+                best_level,
+                worst_level,
+            })
         })
         .collect()
 }
 
+/// Best and worst level by mean response for the optimization type
+///
+/// Nominal-is-best levels are scored by distance to `target`.
+fn best_worst_levels(
+    means: &[f64],
+    optimization_type: &OptimizationType,
+    target: f64,
+) -> (usize, usize) {
+    // Higher score = better level
+    let score = |mean: f64| match optimization_type {
+        OptimizationType::LargerIsBetter => mean,
+        OptimizationType::SmallerIsBetter => -mean,
+        OptimizationType::NominalIsBest => -(mean - target).abs(),
+    };
+    let scored = || {
+        means
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.is_finite())
+            .map(|(level, &m)| (level, score(m)))
+    };
+    let best_level = scored()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(level, _)| level)
+        .unwrap_or(0);
+    let worst_level = scored()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(level, _)| level)
+        .unwrap_or(0);
+
+    (best_level, worst_level)
+}
+
 /// Map library SNRatioEffect to UI SNRatioEffect
 fn map_sn_ratio_effects(
     effects: &[doe::SNRatioEffect],
     factor_ids: &[String],
     factor_names: &[String],
-) -> Vec<SNRatioEffect> {
-    effects
+) -> Result<Vec<SNRatioEffect>, String> {
+    let mut mapped = effects
         .iter()
-        .map(|e| SNRatioEffect {
-            factor_id: factor_ids[e.factor_index].clone(),
-            factor_name: factor_names[e.factor_index].clone(),
-            level_sn_ratios: e.level_sn_ratios.clone(),
-            optimal_level: e.optimal_level,
+        .map(|e| {
+            let (factor_id, factor_name) = factor_label(e.factor_index, factor_ids, factor_names)?;
+            Ok(SNRatioEffect {
+                factor_id,
+                factor_name,
+                level_sn_ratios: e.level_sn_ratios.clone(),
+                optimal_level: e.optimal_level,
+                sn_rank: 0, // Will be set after sorting
+            })
         })
-        .collect()
+        .collect::<Result<Vec<_>, String>>()?;
+
+    // Rank factors by S/N range (larger range = more important = lower rank)
+    let mut ranges: Vec<(usize, f64)> = mapped
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let max = e.level_sn_ratios.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let min = e.level_sn_ratios.iter().copied().fold(f64::INFINITY, f64::min);
+            (i, max - min)
+        })
+        .collect();
+    ranges.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    for (rank, (idx, _)) in ranges.iter().enumerate() {
+        mapped[*idx].sn_rank = rank + 1;
+    }
+
+    Ok(mapped)
+}
+
+/// Check that the library's ANOVA produced usable (finite) values
+fn check_anova(result: &doe::ANOVAResult) -> Result<(), String> {
+    let totals_finite = [result.error_ss, result.error_ms, result.total_ss]
+        .iter()
+        .all(|v| v.is_finite());
+    let entries_finite = result.entries.iter().all(|e| {
+        e.sum_of_squares.is_finite()
+            && e.mean_square.is_finite()
+            && e.f_ratio.is_none_or(f64::is_finite)
+    });
+
+    if totals_finite && entries_finite {
+        Ok(())
+    } else {
+        Err("non-finite sums of squares or F-ratios".to_string())
+    }
+}
+
+/// Order ANOVA entries by factor (library order), or descending contribution or F-ratio
+///
+/// Entries without an F-ratio sort last.
+fn sort_anova_entries(entries: &mut [ANOVAEntry], sort_by: &str) {
+    match sort_by {
+        "contribution" => {
+            entries.sort_by(|a, b| b.contribution_percent.total_cmp(&a.contribution_percent))
+        }
+        "f_ratio" => entries.sort_by(|a, b| match (a.f_ratio, b.f_ratio) {
+            (Some(fa), Some(fb)) => fb.total_cmp(&fa),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }),
+        _ => {}
+    }
 }
 
 /// Map library ANOVAResult to UI ANOVAResult
@@ -160,36 +1740,41 @@ fn map_anova_result(
     result: &doe::ANOVAResult,
     factor_ids: &[String],
     factor_names: &[String],
-) -> ANOVAResult {
+) -> Result<ANOVAResult, String> {
     let entries = result
         .entries
         .iter()
-        .map(|e| ANOVAEntry {
-            factor_id: factor_ids[e.factor_index].clone(),
-            factor_name: factor_names[e.factor_index].clone(),
-            sum_of_squares: e.sum_of_squares,
-            degrees_of_freedom: e.degrees_of_freedom,
-            mean_square: e.mean_square,
-            f_ratio: e.f_ratio,
-            p_value: e.p_value,
-            contribution_percent: e.contribution_percent,
-            pooled: e.pooled,
+        .map(|e| {
+            let (factor_id, factor_name) = factor_label(e.factor_index, factor_ids, factor_names)?;
+            Ok(ANOVAEntry {
+                factor_id,
+                factor_name,
+                sum_of_squares: e.sum_of_squares,
+                degrees_of_freedom: e.degrees_of_freedom,
+                mean_square: e.mean_square,
+                f_ratio: e.f_ratio,
+                p_value: e.p_value,
+                contribution_percent: e.contribution_percent,
+                pooled: e.pooled,
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>, String>>()?;
 
-    ANOVAResult {
+    Ok(ANOVAResult {
         entries,
         error_ss: result.error_ss,
         error_df: result.error_df,
         error_ms: result.error_ms,
         total_ss: result.total_ss,
         total_df: result.total_df,
-    }
+        nested: Vec::new(),
+    })
 }
 
 /// Map library OptimalSettings to UI OptimalSettings
 fn map_optimal_settings(
     settings: &doe::OptimalSettings,
+    sn_ratio_effects: &[doe::SNRatioEffect],
     factor_ids: &[String],
 ) -> OptimalSettings {
     // Convert Vec<usize> to HashMap<String, usize>
@@ -200,9 +1785,16 @@ fn map_optimal_settings(
         .map(|(idx, &level)| (factor_ids[idx].clone(), level))
         .collect();
 
+    let sn_contributions: HashMap<String, f64> = sn_ratio_effects
+        .iter()
+        .filter(|e| e.factor_index < factor_ids.len())
+        .map(|e| (factor_ids[e.factor_index].clone(), sn_contribution(e)))
+        .collect();
+
     let confidence_interval = settings.confidence_interval.map(|ci| ConfidenceInterval {
         lower: ci.lower,
         upper: ci.upper,
+        half_width: (ci.upper - ci.lower) / 2.0,
         level: ci.level,
     });
 
@@ -210,6 +1802,152 @@ fn map_optimal_settings(
         factor_levels,
         predicted_mean: settings.predicted_mean,
         predicted_sn_ratio: settings.predicted_sn_ratio,
+        sn_contributions,
+        sn_optimal_settings: HashMap::new(), // Set by the caller
+        mean_adjustment_factor: None,
         confidence_interval,
     }
 }
+
+/// Split factors for Taguchi's two-step optimization.
+///
+/// The adjustment factor is the one whose effect on the mean most exceeds its
+/// effect on the S/N ratio, with both measured as a share of the largest range;
+/// it is `None` if no factor moves the mean more than the S/N ratio. Every other
+/// factor is a control factor set to its S/N-optimal level.
+fn two_step_settings(
+    main_effects: &[MainEffect],
+    sn_ratio_effects: &[SNRatioEffect],
+) -> (HashMap<String, usize>, Option<String>) {
+    let sn_range = |effect: &SNRatioEffect| {
+        let max = effect.level_sn_ratios.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = effect.level_sn_ratios.iter().copied().fold(f64::INFINITY, f64::min);
+        if max >= min { max - min } else { 0.0 }
+    };
+
+    let max_mean_range = main_effects.iter().map(|e| e.range).fold(0.0, f64::max);
+    let max_sn_range = sn_ratio_effects.iter().map(sn_range).fold(0.0, f64::max);
+
+    let mut adjustment: Option<(String, f64)> = None;
+    if max_mean_range > 0.0 && max_sn_range > 0.0 && main_effects.len() > 1 {
+        for effect in main_effects {
+            let sn_effect = match sn_ratio_effects.iter().find(|sn| sn.factor_id == effect.factor_id) {
+                Some(sn_effect) => sn_effect,
+                None => continue,
+            };
+            let score = effect.range / max_mean_range - sn_range(sn_effect) / max_sn_range;
+            if score > 0.0 && adjustment.as_ref().is_none_or(|(_, best)| score > *best) {
+                adjustment = Some((effect.factor_id.clone(), score));
+            }
+        }
+    }
+    let adjustment = adjustment.map(|(factor_id, _)| factor_id);
+
+    let control_levels = sn_ratio_effects
+        .iter()
+        .filter(|sn| adjustment.as_ref() != Some(&sn.factor_id))
+        .map(|sn| (sn.factor_id.clone(), sn.optimal_level))
+        .collect();
+
+    (control_levels, adjustment)
+}
+
+/// R² and adjusted R² of the main-effects model on the run means.
+///
+/// The model is the unpooled factors, so `R² = Σ SS_factor / SS_total`; with
+/// no replicates this equals `1 - SS_error / SS_total`. Returns `None` when
+/// the total variation or the residual degrees of freedom are zero.
+fn model_fit(anova: &ANOVAResult) -> (Option<f64>, Option<f64>) {
+    if anova.total_ss <= 0.0 || anova.total_df == 0 {
+        return (None, None);
+    }
+
+    let unpooled = anova.entries.iter().filter(|e| !e.pooled);
+    let model_ss: f64 = unpooled.clone().map(|e| e.sum_of_squares).sum();
+    let model_df: usize = unpooled.map(|e| e.degrees_of_freedom).sum();
+
+    let r_squared = model_ss / anova.total_ss;
+    let adj_r_squared = (model_df < anova.total_df).then(|| {
+        1.0 - (1.0 - r_squared) * anova.total_df as f64 / (anova.total_df - model_df) as f64
+    });
+
+    (Some(r_squared), adj_r_squared)
+}
+
+/// S/N gain of a factor's optimal level over the factor's mean S/N.
+///
+/// These are the additive terms of the predicted S/N ratio.
+fn sn_contribution(effect: &doe::SNRatioEffect) -> f64 {
+    let factor_sn_mean =
+        effect.level_sn_ratios.iter().sum::<f64>() / effect.level_sn_ratios.len().max(1) as f64;
+    effect.level_sn_ratios.get(effect.optimal_level).copied().unwrap_or(factor_sn_mean)
+        - factor_sn_mean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curvature_test_matches_textbook_example() {
+        // Montgomery's 2² process-yield example with five center runs
+        let array_data = vec![
+            vec![0, 0],
+            vec![2, 0],
+            vec![0, 2],
+            vec![2, 2],
+            vec![1, 1],
+            vec![1, 1],
+            vec![1, 1],
+            vec![1, 1],
+            vec![1, 1],
+        ];
+        let response_data = [39.3, 40.9, 40.0, 41.5, 40.3, 40.5, 40.7, 40.2, 40.6]
+            .iter()
+            .map(|&y| vec![y])
+            .collect();
+
+        let result = curvature_test(array_data, response_data).unwrap();
+        assert_eq!(result.center_rows, vec![4, 5, 6, 7, 8]);
+        assert_eq!(result.error_df, 4);
+        assert!((result.sum_of_squares - 0.0027).abs() < 1e-4);
+        assert!((result.error_mean_square - 0.0430).abs() < 1e-4);
+        assert!((result.f_value - 0.063).abs() < 1e-3);
+        assert!(result.p_value > 0.8);
+    }
+
+    #[test]
+    fn nested_factor_takes_the_cell_sum_of_squares_within_its_parent() {
+        // L4 with B nested in A; run means 10, 14, 20, 30. The A × B cell SS is
+        // 227 and A's SS is 169, leaving 58 on 2 DOF for B within A
+        let request = DOEAnalysisRequest {
+            array_data: vec![vec![0, 0, 0], vec![0, 1, 1], vec![1, 0, 1], vec![1, 1, 0]],
+            response_data: ResponseData::Replicated(vec![
+                vec![9.0, 11.0],
+                vec![13.0, 15.0],
+                vec![19.0, 21.0],
+                vec![29.0, 31.0],
+            ]),
+            factor_ids: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            factor_names: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            optimization_type: OptimizationType::LargerIsBetter,
+            target_value: None,
+            pooling_threshold: None,
+            enable_pooling: Some(false),
+            min_unpooled_factors: None,
+            confidence_level: None,
+            sn_formula: None,
+            factor_groups: Some(HashMap::from([("a".to_string(), vec!["b".to_string()])])),
+            sort_by: None,
+        };
+
+        let result = run_doe_analysis(request).unwrap();
+        let nested = &result.anova.nested;
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].factor_id, "b");
+        assert_eq!(nested[0].parent_id, "a");
+        assert!((nested[0].sum_of_squares - 58.0).abs() < 1e-9);
+        assert_eq!(nested[0].degrees_of_freedom, 2);
+        assert!((nested[0].mean_square - 29.0).abs() < 1e-9);
+    }
+}
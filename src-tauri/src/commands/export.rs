@@ -1,120 +1,1944 @@
 //! Export and import commands for orthogonal arrays.
 
-use crate::types::OAData;
-use std::path::PathBuf;
+use crate::commands::doe_analysis::get_response_table;
+use crate::commands::info::SCHEMA_VERSION;
+use crate::types::{
+    DOEAnalysis, DOEAnalysisRequest, ImportError, ImportPreview, LatexStyle, OAData, OAMetadata,
+    Project, RunSheetFormat,
+};
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
 
-/// Export an array to CSV format.
+/// Default number of decimals for floating-point values in text exports.
+const DEFAULT_PRECISION: usize = 4;
+
+/// Prefix of the trailing checksum line in exported CSV files.
+const CSV_CHECKSUM_PREFIX: &str = "// sha256: ";
+
+/// Export an array to CSV format, optionally only the given run indices.
+///
+/// Rows are streamed to the file one at a time, so memory stays bounded for
+/// very large designs.
+///
+/// With `include_checksum`, a trailing `// sha256: ...` line records a hash of
+/// the design data for `verify_file_integrity`.
+///
+/// With `include_run_column`, a leading `Run` column numbers the rows 1..runs.
+/// When `rows` gives an execution order, a `DesignRow` column after it records
+/// each run's original (1-based) row in the design.
+///
+/// `delimiter` defaults to `,`; use `;` for locales where the comma is the
+/// decimal separator.
+///
+/// `factor_names` replace the `Factor1..FactorN` headers when there is one per
+/// factor; names containing the delimiter or quotes are quoted per RFC 4180.
+///
+/// `level_labels[factor][level]` replaces coded levels in the output, falling
+/// back to the code where no label is given. The checksum covers the codes,
+/// which a labelled file no longer holds, so labels cannot be combined with
+/// `include_checksum`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_csv(
+    data: OAData,
+    path: PathBuf,
+    rows: Option<Vec<usize>>,
+    include_checksum: Option<bool>,
+    include_run_column: Option<bool>,
+    delimiter: Option<char>,
+    factor_names: Option<Vec<String>>,
+    level_labels: Option<Vec<Vec<String>>>,
+) -> Result<(), String> {
+    check_row_widths(&data)?;
+    let delimiter = csv_delimiter(delimiter)?;
+    let include_checksum = include_checksum.unwrap_or(false);
+    if include_checksum && level_labels.is_some() {
+        return Err("Level labels cannot be combined with a checksum".to_string());
+    }
+    let include_run_column = include_run_column.unwrap_or(false);
+    let include_design_row = include_run_column && rows.is_some();
+    let order: Vec<usize> = match rows {
+        Some(rows) => {
+            validate_row_indices(&rows, data.data.len())?;
+            rows
+        }
+        None => (0..data.data.len()).collect(),
+    };
+
+    let write_error = |e: std::io::Error| format!("Failed to write CSV: {}", e);
+    let file = File::create(&path).map_err(write_error)?;
+    let mut writer = BufWriter::new(file);
+
+    let options = CsvOptions {
+        delimiter,
+        factor_names,
+        level_labels,
+        include_header: true,
+        include_run_column,
+        include_design_row,
+        include_checksum,
+    };
+    write_csv(&mut writer, &data, &order, &options).map_err(write_error)?;
+
+    writer.flush().map_err(write_error)
+}
+
+/// Build the CSV text `export_csv` writes, without touching the disk.
+///
+/// With `include_header` false, the `Factor1..` header row is omitted so the
+/// rows can be pasted into an existing sheet. `factor_names` are used as in
+/// `export_csv`.
+#[tauri::command]
+pub fn export_csv_string(
+    data: OAData,
+    include_header: bool,
+    factor_names: Option<Vec<String>>,
+) -> Result<String, String> {
+    check_row_widths(&data)?;
+    let order: Vec<usize> = (0..data.data.len()).collect();
+    let options = CsvOptions {
+        delimiter: ',',
+        factor_names,
+        level_labels: None,
+        include_header,
+        include_run_column: false,
+        include_design_row: false,
+        include_checksum: false,
+    };
+
+    let mut buffer = Vec::new();
+    write_csv(&mut buffer, &data, &order, &options)
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    String::from_utf8(buffer).map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+/// Export an array as tab-separated values, for pasting into spreadsheets.
+///
+/// `factor_names` are used as in `export_csv`, quoted when they contain a tab.
+#[tauri::command]
+pub async fn export_tsv(
+    data: OAData,
+    path: PathBuf,
+    factor_names: Option<Vec<String>>,
+) -> Result<(), String> {
+    check_row_widths(&data)?;
+    let write_error = |e: std::io::Error| format!("Failed to write TSV: {}", e);
+    let file = File::create(&path).map_err(write_error)?;
+    let mut writer = BufWriter::new(file);
+
+    let order: Vec<usize> = (0..data.data.len()).collect();
+    let options = CsvOptions {
+        delimiter: '\t',
+        factor_names,
+        level_labels: None,
+        include_header: true,
+        include_run_column: false,
+        include_design_row: false,
+        include_checksum: false,
+    };
+    write_csv(&mut writer, &data, &order, &options).map_err(write_error)?;
+
+    writer.flush().map_err(write_error)
+}
+
+/// Export an array to JSON format, optionally only the given run indices.
+///
+/// With `include_checksum`, a `checksum` field records a SHA-256 hash of the
+/// design data for `verify_file_integrity`.
+#[tauri::command]
+pub async fn export_json(
+    data: OAData,
+    path: PathBuf,
+    rows: Option<Vec<usize>>,
+    include_checksum: Option<bool>,
+) -> Result<(), String> {
+    let data = select_rows(data, rows)?;
+    let mut value =
+        serde_json::to_value(&data).map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+    if include_checksum.unwrap_or(false) {
+        if let Some(object) = value.as_object_mut() {
+            object.insert("checksum".to_string(), design_checksum(&data.data).into());
+        }
+    }
+    let json = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write JSON: {}", e))?;
+
+    Ok(())
+}
+
+/// Export several designs to one JSON file, as an array of arrays.
+#[tauri::command]
+pub async fn export_designs_json(designs: Vec<OAData>, path: PathBuf) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&designs)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write JSON: {}", e))?;
+
+    Ok(())
+}
+
+/// Export a run sheet: the design plus blank response columns to fill in by hand.
+///
+/// `format` selects CSV (the default) or an HTML table. `factor_names` replace
+/// the `Factor1..FactorN` headers, and `level_labels[factor][level]` replaces
+/// coded levels, falling back to the code where no label is given. A labelled
+/// sheet is for reading at the bench; `import_run_sheet` expects coded levels.
+#[tauri::command]
+pub fn export_run_sheet(
+    data: OAData,
+    response_names: Vec<String>,
+    replicates: usize,
+    factor_names: Option<Vec<String>>,
+    level_labels: Option<Vec<Vec<String>>>,
+    format: Option<RunSheetFormat>,
+) -> Result<String, String> {
+    check_row_widths(&data)?;
+    if replicates == 0 {
+        return Err("Replicates must be at least 1".to_string());
+    }
+    if let Some(names) = &factor_names {
+        if names.len() != data.factors {
+            return Err(format!(
+                "Got {} factor names, expected {}",
+                names.len(),
+                data.factors
+            ));
+        }
+    }
+    if let Some(labels) = &level_labels {
+        if labels.len() != data.factors {
+            return Err(format!(
+                "Got level labels for {} factors, expected {}",
+                labels.len(),
+                data.factors
+            ));
+        }
+    }
+
+    let response_names = if response_names.is_empty() {
+        vec!["Response".to_string()]
+    } else {
+        response_names
+    };
+
+    let mut headers = vec!["Run".to_string()];
+    match &factor_names {
+        Some(names) => headers.extend(names.iter().cloned()),
+        None => headers.extend((1..=data.factors).map(|i| format!("Factor{}", i))),
+    }
+    for name in &response_names {
+        for rep in 1..=replicates {
+            headers.push(format!("{} R{}", name, rep));
+        }
+    }
+    let blanks = response_names.len() * replicates;
+    let cells = |row: &[u32]| -> Vec<String> {
+        row.iter()
+            .enumerate()
+            .map(|(col, &level)| match level_label(level_labels.as_deref(), col, level) {
+                Some(label) => label.to_string(),
+                None => level.to_string(),
+            })
+            .collect()
+    };
+
+    match format.unwrap_or_default() {
+        RunSheetFormat::Csv => {
+            let mut csv_content = String::new();
+
+            // Header row
+            let headers: Vec<String> = headers.iter().map(|h| escape_csv_field(h)).collect();
+            csv_content.push_str(&headers.join(","));
+            csv_content.push('\n');
+
+            // Data rows with empty response cells
+            for (run, row) in data.data.iter().enumerate() {
+                let row_str: Vec<String> = cells(row).iter().map(|c| escape_csv_field(c)).collect();
+                csv_content.push_str(&format!(
+                    "{},{}{}\n",
+                    run + 1,
+                    row_str.join(","),
+                    ",".repeat(blanks)
+                ));
+            }
+
+            Ok(csv_content)
+        }
+        RunSheetFormat::Html => {
+            let mut html = "<table class=\"run-sheet\">\n".to_string();
+            html.push_str(&format!("  <caption>{}</caption>\n", escape_html(&oa_notation(&data))));
+
+            html.push_str("  <thead>\n    <tr>");
+            for header in &headers {
+                html.push_str(&format!("<th>{}</th>", escape_html(header)));
+            }
+            html.push_str("</tr>\n  </thead>\n");
+
+            // Data rows with empty response cells
+            html.push_str("  <tbody>\n");
+            for (run, row) in data.data.iter().enumerate() {
+                html.push_str(&format!("    <tr><td>{}</td>", run + 1));
+                for cell in cells(row) {
+                    html.push_str(&format!("<td>{}</td>", escape_html(&cell)));
+                }
+                html.push_str(&"<td></td>".repeat(blanks));
+                html.push_str("</tr>\n");
+            }
+            html.push_str("  </tbody>\n</table>\n");
+
+            Ok(html)
+        }
+    }
+}
+
+/// Export DOE analysis results to JSON format.
+#[tauri::command]
+pub async fn export_analysis_json(analysis: DOEAnalysis, path: PathBuf) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&analysis)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write JSON: {}", e))?;
+
+    Ok(())
+}
+
+/// Export DOE analysis results to CSV format (one row per factor level).
+#[tauri::command]
+pub async fn export_analysis_csv(
+    analysis: DOEAnalysis,
+    path: PathBuf,
+    precision: Option<usize>,
+) -> Result<(), String> {
+    let precision = precision.unwrap_or(DEFAULT_PRECISION);
+    let mut csv_content = String::new();
+
+    // Header row
+    csv_content.push_str("Factor,Level,Mean,Effect,SN Ratio\n");
+
+    // Data rows
+    for effect in &analysis.main_effects {
+        let sn_effect = analysis
+            .sn_ratio_effects
+            .iter()
+            .find(|sn| sn.factor_id == effect.factor_id);
+
+        for (level, mean) in effect.level_means.iter().enumerate() {
+            let level_effect = effect.level_effects.get(level).copied().unwrap_or(0.0);
+            let sn_ratio = sn_effect
+                .and_then(|sn| sn.level_sn_ratios.get(level))
+                .map(|v| format_float(*v, precision))
+                .unwrap_or_default();
+
+            csv_content.push_str(&format!(
+                "{},{},{},{},{}\n",
+                escape_csv_field(&effect.factor_name),
+                level + 1,
+                format_float(*mean, precision),
+                format_float(level_effect, precision),
+                sn_ratio
+            ));
+        }
+    }
+
+    std::fs::write(&path, csv_content).map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    Ok(())
+}
+
+/// Export an array to an Excel workbook.
+///
+/// The first sheet holds the array under a `Factor1..FactorN` header, with
+/// levels written as numbers; a "Metadata" sheet records the array's
+/// parameters and provenance.
+#[tauri::command]
+pub async fn export_xlsx(data: OAData, path: PathBuf) -> Result<(), String> {
+    check_row_widths(&data)?;
+    write_design_xlsx(&data, &path).map_err(|e| format!("Failed to write XLSX: {}", e))
+}
+
+/// Export DOE analysis results to an Excel workbook.
+///
+/// Writes one sheet each for the response table, main effects, S/N effects,
+/// and the ANOVA table.
+#[tauri::command]
+pub async fn export_analysis_xlsx(analysis: DOEAnalysis, path: PathBuf) -> Result<(), String> {
+    write_analysis_xlsx(&analysis, &path).map_err(|e| format!("Failed to write XLSX: {}", e))
+}
+
+/// Export DOE analysis results as a plain-text report.
+#[tauri::command]
+pub fn export_analysis_report(
+    analysis: DOEAnalysis,
+    precision: Option<usize>,
+) -> Result<String, String> {
+    let precision = precision.unwrap_or(DEFAULT_PRECISION);
+    let fmt = |v: f64| format_float(v, precision);
+    let mut report = String::new();
+
+    report.push_str("DOE Analysis Report\n");
+    report.push_str(&format!("Analyzed at: {}\n", analysis.analyzed_at));
+    if !analysis.input_hash.is_empty() {
+        report.push_str(&format!("Input hash: {}\n", analysis.input_hash));
+    }
+    report.push('\n');
+    report.push_str(&format!("Grand mean: {}\n", fmt(analysis.grand_mean)));
+    report.push_str(&format!("S/N grand mean: {} dB\n", fmt(analysis.sn_grand_mean)));
+    if !analysis.sn_formula_used.is_empty() {
+        report.push_str(&format!("S/N formula: {}\n", analysis.sn_formula_used));
+    }
+    if let Some(r_squared) = analysis.r_squared {
+        report.push_str(&format!("R²: {}\n", fmt(r_squared)));
+    }
+    if let Some(adj_r_squared) = analysis.adj_r_squared {
+        report.push_str(&format!("Adjusted R²: {}\n", fmt(adj_r_squared)));
+    }
+
+    // Warnings
+    if !analysis.analysis_warnings.is_empty() {
+        report.push_str("\nWarnings\n");
+        for warning in &analysis.analysis_warnings {
+            report.push_str(&format!("  - {}\n", warning));
+        }
+    }
+
+    // Main effects
+    report.push_str("\nMain Effects\n");
+    for effect in &analysis.main_effects {
+        report.push_str(&format!(
+            "  {} (rank {}, range {})\n",
+            effect.factor_name,
+            effect.rank,
+            fmt(effect.range)
+        ));
+        for (level, mean) in effect.level_means.iter().enumerate() {
+            let level_effect = effect.level_effects.get(level).copied().unwrap_or(0.0);
+            report.push_str(&format!(
+                "    Level {}: mean {}, effect {}\n",
+                level + 1,
+                fmt(*mean),
+                fmt(level_effect)
+            ));
+        }
+    }
+
+    // S/N ratios
+    report.push_str("\nS/N Ratios (dB)\n");
+    for effect in &analysis.sn_ratio_effects {
+        let ratios: Vec<String> = effect.level_sn_ratios.iter().map(|v| fmt(*v)).collect();
+        report.push_str(&format!(
+            "  {}: {} (optimal level {})\n",
+            effect.factor_name,
+            ratios.join(", "),
+            effect.optimal_level + 1
+        ));
+    }
+
+    // ANOVA table
+    let anova = &analysis.anova;
+    report.push_str("\nANOVA\n");
+    for entry in &anova.entries {
+        report.push_str(&format!(
+            "  {}: SS {}, DF {}, MS {}, F {}, p {}, contribution {}%{}\n",
+            entry.factor_name,
+            fmt(entry.sum_of_squares),
+            entry.degrees_of_freedom,
+            fmt(entry.mean_square),
+            entry.f_ratio.map(fmt).unwrap_or_else(|| "-".to_string()),
+            entry.p_value.map(fmt).unwrap_or_else(|| "-".to_string()),
+            fmt(entry.contribution_percent),
+            if entry.pooled { " (pooled)" } else { "" }
+        ));
+    }
+    report.push_str(&format!(
+        "  Error: SS {}, DF {}, MS {}\n",
+        fmt(anova.error_ss),
+        anova.error_df,
+        fmt(anova.error_ms)
+    ));
+    report.push_str(&format!(
+        "  Total: SS {}, DF {}\n",
+        fmt(anova.total_ss),
+        anova.total_df
+    ));
+
+    // Optimal settings
+    let optimal = &analysis.optimal_settings;
+    report.push_str("\nOptimal Settings\n");
+    for effect in &analysis.main_effects {
+        if let Some(level) = optimal.factor_levels.get(&effect.factor_id) {
+            report.push_str(&format!("  {}: level {}\n", effect.factor_name, level + 1));
+        }
+    }
+    report.push_str(&format!("  Predicted mean: {}\n", fmt(optimal.predicted_mean)));
+    report.push_str(&format!(
+        "  Predicted S/N ratio: {} dB\n",
+        fmt(optimal.predicted_sn_ratio)
+    ));
+    if let Some(ci) = &optimal.confidence_interval {
+        report.push_str(&format!(
+            "  {}% confidence interval: [{}, {}] (± {})\n",
+            fmt(ci.level * 100.0),
+            fmt(ci.lower),
+            fmt(ci.upper),
+            fmt(ci.half_width)
+        ));
+    }
+
+    Ok(report)
+}
+
+/// Export the Taguchi response table as CSV, Markdown, or LaTeX.
+///
+/// One row per level with the mean response for each factor, followed by the
+/// Delta (max - min) and Rank rows.
+#[tauri::command]
+pub fn export_response_table(request: DOEAnalysisRequest, format: String) -> Result<String, String> {
+    let table = get_response_table(request)?;
+    let fmt = |v: f64| format_float(v, DEFAULT_PRECISION);
+
+    let max_levels = table.factors.iter().map(|f| f.level_means.len()).max().unwrap_or(0);
+    let deltas: Vec<f64> = table
+        .factors
+        .iter()
+        .map(|f| {
+            let max = f.level_means.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let min = f.level_means.iter().copied().fold(f64::INFINITY, f64::min);
+            max - min
+        })
+        .collect();
+    let mut ranks = vec![0; deltas.len()];
+    let mut order: Vec<usize> = (0..deltas.len()).collect();
+    order.sort_by(|&a, &b| deltas[b].total_cmp(&deltas[a]));
+    for (rank, idx) in order.into_iter().enumerate() {
+        ranks[idx] = rank + 1;
+    }
+
+    // Cells: header, one row per level, then Delta and Rank
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut header = vec!["Level".to_string()];
+    header.extend(table.factors.iter().map(|f| f.factor_name.clone()));
+    rows.push(header);
+    for level in 0..max_levels {
+        let mut row = vec![(level + 1).to_string()];
+        row.extend(
+            table
+                .factors
+                .iter()
+                .map(|f| f.level_means.get(level).map(|&m| fmt(m)).unwrap_or_default()),
+        );
+        rows.push(row);
+    }
+    let mut delta_row = vec!["Delta".to_string()];
+    delta_row.extend(deltas.iter().map(|&d| fmt(d)));
+    rows.push(delta_row);
+    let mut rank_row = vec!["Rank".to_string()];
+    rank_row.extend(ranks.iter().map(|r| r.to_string()));
+    rows.push(rank_row);
+
+    let mut output = String::new();
+    match format.as_str() {
+        "csv" => {
+            for row in &rows {
+                let cells: Vec<String> = row.iter().map(|c| escape_csv_field(c)).collect();
+                output.push_str(&cells.join(","));
+                output.push('\n');
+            }
+        }
+        "markdown" => {
+            for (i, row) in rows.iter().enumerate() {
+                let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+                output.push_str(&format!("| {} |\n", cells.join(" | ")));
+                if i == 0 {
+                    output.push_str(&format!("|{}\n", "---|".repeat(row.len())));
+                }
+            }
+        }
+        "latex" => {
+            let col_spec = format!("|{}|", "c|".repeat(table.factors.len() + 1));
+            output.push_str(&format!("\\begin{{tabular}}{{{}}}\n", col_spec));
+            output.push_str("\\hline\n");
+            for (i, row) in rows.iter().enumerate() {
+                let cells: Vec<String> = row.iter().map(|c| escape_latex(c)).collect();
+                output.push_str(&cells.join(" & "));
+                output.push_str(" \\\\\n");
+                // Rules below the header and above Delta
+                if i == 0 || i == max_levels {
+                    output.push_str("\\hline\n");
+                }
+            }
+            output.push_str("\\hline\n");
+            output.push_str("\\end{tabular}\n");
+        }
+        _ => {
+            return Err(format!(
+                "Unknown format '{}' (expected \"csv\", \"markdown\", or \"latex\")",
+                format
+            ))
+        }
+    }
+
+    Ok(output)
+}
+
+/// Export an array to LaTeX tabular format.
+///
+/// `style` is `Grid` (default, `\hline` and vertical rules) or `Booktabs`
+/// (`\toprule`/`\midrule`/`\bottomrule`, no vertical rules, wrapped in a
+/// `table` captioned with the OA notation; needs `\usepackage{booktabs}`).
+/// Optional `factor_names` replace the `$F_i$` headers and
+/// `level_labels[factor][level]` replace level numbers.
+///
+/// `responses` (runs × replicates) adds `$y_1$..$y_r$` columns after the
+/// factors, plus `$\bar{y}$` when there are several replicates, and
+/// `sn_ratios` adds an `$S/N$` column. Values are printed with `precision`
+/// decimals (default 4); missing or NaN values print as `--`.
+///
+/// Inconsistencies between `data.levels` and the values in `data.data` are
+/// reported as `% Warning:` comments, since they make the OA notation wrong.
+#[tauri::command]
+pub fn export_latex(
+    data: OAData,
+    style: Option<LatexStyle>,
+    factor_names: Option<Vec<String>>,
+    level_labels: Option<Vec<Vec<String>>>,
+    responses: Option<Vec<Vec<f64>>>,
+    sn_ratios: Option<Vec<f64>>,
+    precision: Option<usize>,
+) -> Result<String, String> {
+    let booktabs = style.unwrap_or_default() == LatexStyle::Booktabs;
+    let precision = precision.unwrap_or(DEFAULT_PRECISION);
+    check_row_widths(&data)?;
+    if let Some(names) = &factor_names {
+        if names.len() != data.factors {
+            return Err(format!(
+                "Got {} factor names, expected {}",
+                names.len(),
+                data.factors
+            ));
+        }
+    }
+    if let Some(labels) = &level_labels {
+        if labels.len() != data.factors {
+            return Err(format!(
+                "Got level labels for {} factors, expected {}",
+                labels.len(),
+                data.factors
+            ));
+        }
+    }
+    if let Some(responses) = &responses {
+        if responses.len() != data.data.len() {
+            return Err(format!(
+                "Got responses for {} runs, expected {}",
+                responses.len(),
+                data.data.len()
+            ));
+        }
+    }
+    if let Some(sn_ratios) = &sn_ratios {
+        if sn_ratios.len() != data.data.len() {
+            return Err(format!(
+                "Got S/N ratios for {} runs, expected {}",
+                sn_ratios.len(),
+                data.data.len()
+            ));
+        }
+    }
+
+    // Extra columns after the factors: replicates, their mean, and S/N
+    let replicates = responses
+        .as_ref()
+        .map_or(0, |responses| responses.iter().map(|reps| reps.len()).max().unwrap_or(0));
+    let include_mean = replicates > 1;
+    let columns = data.factors
+        + replicates
+        + usize::from(include_mean)
+        + usize::from(sn_ratios.is_some());
+    let fmt = |value: Option<f64>| match value {
+        Some(v) if v.is_finite() => format_float(v, precision),
+        _ => "--".to_string(),
+    };
+
+    let (top_rule, mid_rule, bottom_rule) = if booktabs {
+        ("\\toprule\n", "\\midrule\n", "\\bottomrule\n")
+    } else {
+        ("\\hline\n", "\\hline\n", "\\hline\n")
+    };
+
+    let mut latex = String::new();
+    if booktabs {
+        latex.push_str("\\begin{table}[htbp]\n\\centering\n");
+        latex.push_str(&format!("\\caption{{{}}}\n", escape_latex(&oa_notation(&data))));
+    }
+
+    // Begin tabular environment
+    let col_spec = if booktabs {
+        "c".repeat(columns)
+    } else {
+        format!("|{}|", "c|".repeat(columns))
+    };
+    latex.push_str(&format!("\\begin{{tabular}}{{{}}}\n", col_spec));
+    latex.push_str(top_rule);
+
+    // Header row
+    let mut headers: Vec<String> = match &factor_names {
+        Some(names) => names.iter().map(|name| escape_latex(name)).collect(),
+        None => (1..=data.factors).map(|i| format!("$F_{{{}}}$", i)).collect(),
+    };
+    headers.extend((1..=replicates).map(|i| format!("$y_{{{}}}$", i)));
+    if include_mean {
+        headers.push("$\\bar{y}$".to_string());
+    }
+    if sn_ratios.is_some() {
+        headers.push("$S/N$".to_string());
+    }
+    latex.push_str(&headers.join(" & "));
+    latex.push_str(" \\\\\n");
+    latex.push_str(mid_rule);
+
+    // Data rows
+    for (run, row) in data.data.iter().enumerate() {
+        let mut row_str: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(col, v)| {
+                level_labels
+                    .as_ref()
+                    .and_then(|labels| labels[col].get(*v as usize))
+                    .map(|label| escape_latex(label))
+                    .unwrap_or_else(|| v.to_string())
+            })
+            .collect();
+        if let Some(responses) = &responses {
+            let reps = &responses[run];
+            row_str.extend((0..replicates).map(|i| fmt(reps.get(i).copied())));
+            if include_mean {
+                let values: Vec<f64> = reps.iter().copied().filter(|v| v.is_finite()).collect();
+                let mean = (!values.is_empty())
+                    .then(|| values.iter().sum::<f64>() / values.len() as f64);
+                row_str.push(fmt(mean));
+            }
+        }
+        if let Some(sn_ratios) = &sn_ratios {
+            row_str.push(fmt(Some(sn_ratios[run])));
+        }
+        latex.push_str(&row_str.join(" & "));
+        latex.push_str(" \\\\\n");
+    }
+
+    latex.push_str(bottom_rule);
+    latex.push_str("\\end{tabular}\n");
+    if booktabs {
+        latex.push_str("\\end{table}\n");
+    }
+
+    // Add caption with array notation
+    latex.push_str(&format!("\n% {}\n", oa_notation(&data)));
+
+    if let Some(name) = &data.metadata.name {
+        latex.push_str(&format!("% Name: {}\n", name));
+    }
+    latex.push_str(&format!("% Algorithm: {}\n", data.metadata.algorithm));
+
+    for warning in level_mismatch_warnings(&data) {
+        latex.push_str(&format!("% Warning: {}\n", warning));
+    }
+
+    Ok(latex)
+}
+
+/// Export an array as a GitHub-flavored Markdown table.
+///
+/// A bold `OA(runs, factors, levels, strength)` caption precedes the table,
+/// whose first column numbers the runs from 1. `level_labels[factor][level]`
+/// replaces coded levels, falling back to the code where no label is given.
+#[tauri::command]
+pub fn export_markdown(
+    data: OAData,
+    level_labels: Option<Vec<Vec<String>>>,
+) -> Result<String, String> {
+    check_row_widths(&data)?;
+
+    let mut markdown = format!("**{}**\n\n", oa_notation(&data));
+
+    let mut header = vec!["Run".to_string()];
+    header.extend((1..=data.factors).map(|i| format!("Factor{}", i)));
+    markdown.push_str(&format!("| {} |\n", header.join(" | ")));
+    markdown.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+
+    for (run, row) in data.data.iter().enumerate() {
+        let mut cells = vec![(run + 1).to_string()];
+        cells.extend(row.iter().enumerate().map(|(col, &level)| {
+            match level_label(level_labels.as_deref(), col, level) {
+                Some(label) => label.replace('|', "\\|"),
+                None => level.to_string(),
+            }
+        }));
+        markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+
+    Ok(markdown)
+}
+
+/// Export an array as an HTML `<table>`.
+///
+/// The OA notation is the `<caption>`, and `css_class`, when given, is set as
+/// the table's `class` attribute. All text is HTML-escaped.
+#[tauri::command]
+pub fn export_html(data: OAData, css_class: Option<String>) -> Result<String, String> {
+    check_row_widths(&data)?;
+
+    let mut html = match &css_class {
+        Some(class) => format!("<table class=\"{}\">\n", escape_html(class)),
+        None => "<table>\n".to_string(),
+    };
+    html.push_str(&format!("  <caption>{}</caption>\n", escape_html(&oa_notation(&data))));
+
+    html.push_str("  <thead>\n    <tr>");
+    for i in 1..=data.factors {
+        html.push_str(&format!("<th>{}</th>", escape_html(&format!("Factor{}", i))));
+    }
+    html.push_str("</tr>\n  </thead>\n");
+
+    html.push_str("  <tbody>\n");
+    for row in &data.data {
+        html.push_str("    <tr>");
+        for value in row {
+            html.push_str(&format!("<td>{}</td>", escape_html(&value.to_string())));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("  </tbody>\n</table>\n");
+
+    Ok(html)
+}
+
+/// Export an R script that builds the array as a `data.frame`.
+///
+/// Every factor column is wrapped in `factor()` so R treats the levels as
+/// categories. Commented-out `aov()` scaffolding fits the main-effects model
+/// once a `response` vector (one value per run, in run order) is defined.
+#[tauri::command]
+pub fn export_r_script(data: OAData) -> Result<String, String> {
+    if data.factors == 0 {
+        return Err("Array must have at least one factor".to_string());
+    }
+    check_row_widths(&data)?;
+
+    let mut script = format!("# {}\n", oa_notation(&data));
+    if let Some(name) = &data.metadata.name {
+        script.push_str(&format!("# Name: {}\n", name.replace('\n', " ")));
+    }
+    script.push_str(&format!("# Algorithm: {}\n\n", data.metadata.algorithm));
+
+    let names: Vec<String> = (1..=data.factors).map(|i| format!("Factor{}", i)).collect();
+    let columns: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(col, name)| {
+            let values: Vec<String> = data.data.iter().map(|row| row[col].to_string()).collect();
+            format!("  {} = factor(c({}))", name, values.join(", "))
+        })
+        .collect();
+    script.push_str("design <- data.frame(\n");
+    script.push_str(&columns.join(",\n"));
+    script.push_str("\n)\n\n");
+
+    script.push_str(&format!(
+        "# Define one response value per run, in run order:\n# response <- c({})\n",
+        vec!["NA"; data.runs].join(", ")
+    ));
+    script.push_str("# design$response <- response\n");
+    script.push_str(&format!(
+        "# fit <- aov(response ~ {}, data = design)\n",
+        names.join(" + ")
+    ));
+    script.push_str("# summary(fit)\n");
+
+    Ok(script)
+}
+
+/// Export a Python snippet that builds the array as a `pandas.DataFrame`.
+///
+/// Columns are named from `factor_names` when given, otherwise
+/// `Factor1..FactorN`. A commented line shows how to attach a `response`
+/// Series with one value per run.
+#[tauri::command]
+pub fn export_python(data: OAData, factor_names: Option<Vec<String>>) -> Result<String, String> {
+    check_row_widths(&data)?;
+    let names = match factor_names {
+        Some(names) if names.len() != data.factors => {
+            return Err(format!(
+                "Got {} factor names, expected {}",
+                names.len(),
+                data.factors
+            ))
+        }
+        Some(names) => names,
+        None => (1..=data.factors).map(|i| format!("Factor{}", i)).collect(),
+    };
+
+    // JSON string literals are valid Python string literals
+    let columns: Vec<String> = names
+        .iter()
+        .map(|name| serde_json::to_string(name).map_err(|e| format!("Failed to quote name: {}", e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut script = format!("# {}\n", oa_notation(&data));
+    script.push_str("import pandas as pd\n\n");
+    script.push_str("rows = [\n");
+    for row in &data.data {
+        let cells: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        script.push_str(&format!("    [{}],\n", cells.join(", ")));
+    }
+    script.push_str("]\n");
+    script.push_str(&format!(
+        "design = pd.DataFrame(rows, columns=[{}])\n\n",
+        columns.join(", ")
+    ));
+    script.push_str("# One response value per run, in run order:\n");
+    script.push_str("# design = design.join(pd.Series(response, name=\"response\"))\n");
+
+    Ok(script)
+}
+
+/// Export an array for JMP.
+///
+/// A `.jsl` path gets a JSL script that builds the data table with every
+/// factor column marked as Nominal. Any other path gets a tab-delimited table
+/// led by a JMP-style header comment and a column-properties comment marking
+/// each factor Nominal, followed by the column names and one row per run.
+#[tauri::command]
+pub async fn export_jmp(data: OAData, path: PathBuf) -> Result<(), String> {
+    check_row_widths(&data)?;
+    let is_jsl = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("jsl"));
+    let content = if is_jsl {
+        jmp_script(&data)
+    } else {
+        jmp_table(&data)
+    };
+
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write JMP file: {}", e))?;
+
+    Ok(())
+}
+
+/// Export an array as SQL `CREATE TABLE` and `INSERT` statements.
+#[tauri::command]
+pub fn export_sql(data: OAData, table_name: String) -> Result<String, String> {
+    check_row_widths(&data)?;
+    let table_name = if table_name.trim().is_empty() {
+        "experiment_design".to_string()
+    } else {
+        table_name.trim().to_string()
+    };
+    // Quote the identifier, doubling any embedded quotes
+    let table = format!("\"{}\"", table_name.replace('"', "\"\""));
+
+    let mut sql = String::new();
+
+    // Table definition
+    let mut columns = vec!["    run_id INTEGER PRIMARY KEY".to_string()];
+    columns.extend((1..=data.factors).map(|i| format!("    factor{} INTEGER NOT NULL", i)));
+    sql.push_str(&format!("CREATE TABLE {} (\n", table));
+    sql.push_str(&columns.join(",\n"));
+    sql.push_str("\n);\n\n");
+
+    // One INSERT per run
+    let column_names: Vec<String> = std::iter::once("run_id".to_string())
+        .chain((1..=data.factors).map(|i| format!("factor{}", i)))
+        .collect();
+    for (run, row) in data.data.iter().enumerate() {
+        let values: Vec<String> = std::iter::once((run + 1).to_string())
+            .chain(row.iter().map(|v| v.to_string()))
+            .collect();
+        sql.push_str(&format!(
+            "INSERT INTO {} ({}) VALUES ({});\n",
+            table,
+            column_names.join(", "),
+            values.join(", ")
+        ));
+    }
+
+    Ok(sql)
+}
+
+/// Transpose an array to factors × runs for wide-format display.
+///
+/// Errors if any row does not have one column per factor.
+#[tauri::command]
+pub fn transpose_design(data: OAData) -> Result<Vec<Vec<u32>>, String> {
+    check_row_widths(&data)?;
+
+    Ok((0..data.factors)
+        .map(|col| data.data.iter().map(|row| row[col]).collect())
+        .collect())
+}
+
+/// Reshape a design and its replicate responses into a long-format table.
+///
+/// Returns a header row followed by one row per run × replicate:
+/// run number, the run's factor levels, replicate number, and response.
+/// Missing (NaN) responses are left blank.
+#[tauri::command]
+pub fn flatten_responses(
+    array_data: Vec<Vec<u32>>,
+    response_data: Vec<Vec<f64>>,
+) -> Result<Vec<Vec<String>>, String> {
+    if array_data.len() != response_data.len() {
+        return Err(format!(
+            "Response data has {} runs, expected {}",
+            response_data.len(),
+            array_data.len()
+        ));
+    }
+
+    let factors = array_data.first().map(|row| row.len()).unwrap_or(0);
+    let mut header = vec!["Run".to_string()];
+    header.extend((1..=factors).map(|i| format!("Factor{}", i)));
+    header.extend(["Replicate".to_string(), "Response".to_string()]);
+
+    let mut table = vec![header];
+    for (run, (levels, responses)) in array_data.iter().zip(response_data.iter()).enumerate() {
+        for (rep, value) in responses.iter().enumerate() {
+            let mut row = vec![(run + 1).to_string()];
+            row.extend(levels.iter().map(|v| v.to_string()));
+            row.push((rep + 1).to_string());
+            row.push(if value.is_nan() { String::new() } else { value.to_string() });
+            table.push(row);
+        }
+    }
+
+    Ok(table)
+}
+
+/// Import an array from CSV file (optionally gzip-compressed).
+///
+/// `delimiter` defaults to `,`.
+#[tauri::command]
+pub async fn import_csv(path: PathBuf, delimiter: Option<char>) -> Result<Vec<Vec<u32>>, String> {
+    let delimiter = csv_delimiter(delimiter)?;
+    let content = read_import_file(&path)?;
+    let (_, data) = parse_design_table(&content, delimiter)?;
+    Ok(data)
+}
+
+/// Import an array from a worksheet of an Excel workbook.
+///
+/// Reads the named sheet, or the first one. As with `import_csv`, a
+/// non-numeric first row is treated as a header and skipped. Empty trailing
+/// cells and rows are ignored.
+#[tauri::command]
+pub async fn import_xlsx(path: PathBuf, sheet: Option<String>) -> Result<Vec<Vec<u32>>, String> {
+    read_design_sheet(&path, sheet.as_deref())
+}
+
+/// Import an array from a tab-separated file (optionally gzip-compressed).
+///
+/// Uses the same header detection and row-length checks as `import_csv`.
+#[tauri::command]
+pub async fn import_tsv(path: PathBuf) -> Result<Vec<Vec<u32>>, String> {
+    let content = read_import_file(&path)?;
+    let (_, data) = parse_design_table(&content, '\t')?;
+    Ok(data)
+}
+
+/// Import an array from CSV file, keeping the header row's column names.
+///
+/// Files without a header row get the default names "Factor1", "Factor2", ...
+/// `delimiter` defaults to `,`.
+#[tauri::command]
+pub async fn import_csv_with_headers(
+    path: PathBuf,
+    delimiter: Option<char>,
+) -> Result<(Vec<String>, Vec<Vec<u32>>), String> {
+    let delimiter = csv_delimiter(delimiter)?;
+    let content = read_import_file(&path)?;
+    let (header, data) = parse_design_table(&content, delimiter)?;
+
+    let names = match header {
+        Some(names) => {
+            if names.len() != data[0].len() {
+                return Err(format!(
+                    "Header has {} columns, but the data has {}",
+                    names.len(),
+                    data[0].len()
+                ));
+            }
+            names
+        }
+        None => (1..=data[0].len()).map(|i| format!("Factor{}", i)).collect(),
+    };
+
+    Ok((names, data))
+}
+
+/// Import a filled-in run sheet, splitting it into design and response data.
+///
+/// Expects the layout written by `export_run_sheet`: a run number column, then
+/// `num_factor_cols` factor columns, then `num_responses × replicates` response
+/// columns grouped by response. Blank cells are returned as NaN. Fields may be
+/// quoted per RFC 4180, and gzipped files are decompressed transparently.
+#[tauri::command]
+pub async fn import_run_sheet(
+    path: PathBuf,
+    num_factor_cols: usize,
+    num_responses: usize,
+    replicates: usize,
+) -> Result<(Vec<Vec<u32>>, Vec<Vec<f64>>), String> {
+    let content = read_import_file(&path)?;
+
+    let num_response_cols = num_responses * replicates;
+    let mut design: Vec<Vec<u32>> = Vec::new();
+    let mut responses: Vec<Vec<f64>> = Vec::new();
+
+    let mut records = split_records(&content, ',')
+        .into_iter()
+        .enumerate()
+        .filter(|(_, record)| record.iter().any(|s| !s.trim().is_empty()))
+        .peekable();
+
+    // Skip header row (first cell of the first non-empty record is not a run number)
+    if records
+        .peek()
+        .is_some_and(|(_, record)| record[0].trim().parse::<usize>().is_err())
+    {
+        records.next();
+    }
+
+    for (row_idx, record) in records {
+        let cells: Vec<&str> = record.iter().map(|s| s.trim()).collect();
+
+        if cells.len() < 1 + num_factor_cols {
+            return Err(format!(
+                "Row {} has {} columns, expected at least {}",
+                row_idx + 1,
+                cells.len(),
+                1 + num_factor_cols
+            ));
+        }
+        if cells.len() > 1 + num_factor_cols + num_response_cols {
+            return Err(format!(
+                "Row {} has {} columns, expected at most {}",
+                row_idx + 1,
+                cells.len(),
+                1 + num_factor_cols + num_response_cols
+            ));
+        }
+
+        let row: Vec<u32> = cells[1..=num_factor_cols]
+            .iter()
+            .map(|s| {
+                s.parse::<u32>()
+                    .map_err(|e| format!("Invalid level '{}' on row {}: {}", s, row_idx + 1, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Missing trailing cells are treated as blank
+        let values: Vec<f64> = (0..num_response_cols)
+            .map(|i| match cells.get(1 + num_factor_cols + i) {
+                Some(s) if !s.is_empty() => s.parse::<f64>().map_err(|e| {
+                    format!("Invalid response '{}' on row {}: {}", s, row_idx + 1, e)
+                }),
+                _ => Ok(f64::NAN),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        design.push(row);
+        responses.push(values);
+    }
+
+    if design.is_empty() {
+        return Err("No data found in file".to_string());
+    }
+
+    Ok((design, responses))
+}
+
+/// Merge per-replicate CSV files into one runs × replicates response matrix.
+///
+/// Each file holds a single response column with one value per run, and an
+/// optional header line. Files become replicate columns in the order given.
+#[tauri::command]
+pub async fn merge_replicate_files(paths: Vec<PathBuf>) -> Result<Vec<Vec<f64>>, String> {
+    if paths.is_empty() {
+        return Err("At least one file must be given".to_string());
+    }
+
+    let mut merged: Vec<Vec<f64>> = Vec::new();
+
+    for (file_idx, path) in paths.iter().enumerate() {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
+
+        let mut values: Vec<f64> = Vec::new();
+        for (line_idx, line) in content.lines().enumerate() {
+            let cell = line.trim();
+            if cell.is_empty() {
+                continue;
+            }
+
+            match cell.parse::<f64>() {
+                Ok(value) => values.push(value),
+                // Skip header row
+                Err(_) if line_idx == 0 => {}
+                Err(e) => {
+                    return Err(format!(
+                        "Invalid response '{}' on line {} of {}: {}",
+                        cell,
+                        line_idx + 1,
+                        path.display(),
+                        e
+                    ))
+                }
+            }
+        }
+
+        if values.is_empty() {
+            return Err(format!("No data found in file {}", path.display()));
+        }
+
+        if file_idx == 0 {
+            merged = values.into_iter().map(|v| vec![v]).collect();
+        } else if values.len() != merged.len() {
+            return Err(format!(
+                "Run count mismatch: {} has {} runs, expected {}",
+                path.display(),
+                values.len(),
+                merged.len()
+            ));
+        } else {
+            for (row, value) in merged.iter_mut().zip(values) {
+                row.push(value);
+            }
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Import an array from JSON file (optionally gzip-compressed).
+///
+/// Accepts a full `OAData` document or a bare matrix such as `[[0,1],[1,0]]`.
+/// For a bare matrix the metadata is synthesized from the detected parameters
+/// and a warning is added.
+///
+/// Parse failures carry the line and column of the error.
+#[tauri::command]
+pub async fn import_json(path: PathBuf) -> Result<OAData, ImportError> {
+    let content = read_import_file(&path)?;
+
+    let oa_error = match serde_json::from_str::<OAData>(&content) {
+        Ok(data) => return Ok(data),
+        Err(e) => e,
+    };
+
+    // Fall back to a bare matrix
+    let matrix: Vec<Vec<u32>> = match serde_json::from_str(&content) {
+        Ok(matrix) => matrix,
+        Err(_) => return Err(oa_error.into()),
+    };
+    let validation = validate_import(matrix.clone())?;
+
+    let mut warnings = validation.warnings;
+    warnings.push("File contained only the array; metadata was synthesized".to_string());
+
+    Ok(OAData {
+        id: Uuid::new_v4().to_string(),
+        runs: validation.runs,
+        factors: validation.factors,
+        levels: validation.levels,
+        strength: validation.estimated_strength,
+        data: matrix,
+        metadata: OAMetadata {
+            name: None,
+            algorithm: "Imported".to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            notes: None,
+        },
+        warnings,
+    })
+}
+
+/// Import several designs from a JSON file written by `export_designs_json`.
+#[tauri::command]
+pub async fn import_designs_json(path: PathBuf) -> Result<Vec<OAData>, ImportError> {
+    let content = read_import_file(&path)?;
+
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Save designs, their response data, and analyses as one project file.
+///
+/// The project is written as JSON (conventionally with a `.taguchi`
+/// extension) stamped with the current schema version. `responses` maps design
+/// IDs to runs × replicates data and must match the designs' run counts.
+#[tauri::command]
+pub async fn save_project(
+    path: PathBuf,
+    designs: Vec<OAData>,
+    analyses: Vec<DOEAnalysis>,
+    responses: Option<HashMap<String, Vec<Vec<f64>>>>,
+) -> Result<(), String> {
+    let responses = responses.unwrap_or_default();
+    for (design_id, data) in &responses {
+        let design = match designs.iter().find(|d| &d.id == design_id) {
+            Some(design) => design,
+            None => return Err(format!("Responses refer to unknown design '{}'", design_id)),
+        };
+        if data.len() != design.runs {
+            return Err(format!(
+                "Responses for design '{}' have {} runs, expected {}",
+                design_id,
+                data.len(),
+                design.runs
+            ));
+        }
+    }
+
+    let project = Project {
+        schema_version: SCHEMA_VERSION,
+        saved_at: Utc::now().to_rfc3339(),
+        designs,
+        responses,
+        analyses,
+    };
+    let json = serde_json::to_string_pretty(&project)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write project: {}", e))?;
+
+    Ok(())
+}
+
+/// Load a project file written by `save_project` (optionally gzip-compressed).
+///
+/// Files from a newer schema version are rejected rather than partially read.
+#[tauri::command]
+pub async fn load_project(path: PathBuf) -> Result<Project, ImportError> {
+    let content = read_import_file(&path)?;
+
+    // Check the version first, so newer files fail with a clear message
+    let value: serde_json::Value = serde_json::from_str(&content)?;
+    let schema_version = match value.get("schemaVersion").and_then(|v| v.as_u64()) {
+        Some(version) => version,
+        None => return Err("File is not a project (no schema version)".to_string().into()),
+    };
+    if schema_version > SCHEMA_VERSION as u64 {
+        return Err(format!(
+            "Project was saved with schema version {}, but this version supports up to {}",
+            schema_version, SCHEMA_VERSION
+        )
+        .into());
+    }
+
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Import DOE analysis results from JSON file.
+#[tauri::command]
+pub async fn import_analysis_json(path: PathBuf) -> Result<DOEAnalysis, String> {
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+/// Validate imported array data.
+#[tauri::command]
+pub fn validate_import(data: Vec<Vec<u32>>) -> Result<crate::types::ImportValidation, String> {
+    if data.is_empty() {
+        return Err("Array data is empty".to_string());
+    }
+
+    let runs = data.len();
+    let factors = data[0].len();
+
+    // Check all rows have same length
+    for (i, row) in data.iter().enumerate() {
+        if row.len() != factors {
+            return Err(format!(
+                "Row {} has {} columns, expected {}",
+                i + 1,
+                row.len(),
+                factors
+            ));
+        }
+    }
+
+    // Detect levels per factor
+    let mut levels = vec![0u32; factors];
+    for col in 0..factors {
+        let max_val = data.iter().map(|row| row[col]).max().unwrap_or(0);
+        levels[col] = max_val + 1;
+    }
+
+    // Check for consistent levels (pure vs mixed)
+    let is_mixed = levels.iter().collect::<std::collections::HashSet<_>>().len() > 1;
+
+    // Estimate strength by checking balance
+    let estimated_strength = estimate_strength(&data, &levels);
+
+    let duplicate_columns = find_duplicate_columns(&data, factors);
+    let mut warnings = generate_warnings(&data, &levels);
+    for group in &duplicate_columns {
+        let cols: Vec<String> = group.iter().map(|col| (col + 1).to_string()).collect();
+        warnings.push(format!(
+            "Factors {} are aliased (identical up to relabeling); the array is degenerate",
+            cols.join(", ")
+        ));
+    }
+
+    let center_point_rows = find_center_points(&data, &levels);
+    if !center_point_rows.is_empty() {
+        let rows: Vec<String> = center_point_rows.iter().map(|row| (row + 1).to_string()).collect();
+        warnings.push(format!(
+            "Rows {} are center points (every factor at its middle level)",
+            rows.join(", ")
+        ));
+    }
+
+    Ok(crate::types::ImportValidation {
+        runs,
+        factors,
+        levels: levels.clone(),
+        is_mixed,
+        estimated_strength,
+        duplicate_columns,
+        center_point_rows,
+        warnings,
+    })
+}
+
+/// Preview a design file without importing it.
+///
+/// The format is detected from the extension (ignoring a trailing `.gz`),
+/// falling back to the content for unknown extensions. CSV and JSON files
+/// (optionally gzip-compressed), TSV files, and XLSX workbooks are supported; YAML files
+/// are recognized but rejected, since they cannot be imported. JSON files may
+/// hold an `OAData` document or a bare matrix, and workbooks are read from the
+/// first sheet.
+#[tauri::command]
+pub async fn preview_import(path: PathBuf) -> Result<ImportPreview, String> {
+    let extension = import_extension(&path);
+    let (format, data) = match extension.as_str() {
+        "xlsx" | "xlsm" | "xls" | "ods" => ("xlsx", read_design_sheet(&path, None)?),
+        "yaml" | "yml" => {
+            return Err("YAML files cannot be imported; use CSV, JSON, or XLSX".to_string())
+        }
+        _ => preview_text_import(&path, &extension)?,
+    };
+
+    let validation = validate_import(data)?;
+
+    Ok(ImportPreview {
+        format: format.to_string(),
+        runs: validation.runs,
+        factors: validation.factors,
+        levels: validation.levels,
+        is_mixed: validation.is_mixed,
+        warnings: validation.warnings,
+    })
+}
+
+/// Parse a CSV or JSON file for `preview_import`, returning its format and data.
+fn preview_text_import(
+    path: &Path,
+    extension: &str,
+) -> Result<(&'static str, Vec<Vec<u32>>), String> {
+    let content = read_import_file(path)?;
+    if extension == "tsv" {
+        let (_, data) = parse_design_table(&content, '\t')?;
+        return Ok(("tsv", data));
+    }
+    let is_json = match extension {
+        "json" => true,
+        "csv" | "txt" => false,
+        _ => content.trim_start().starts_with(['{', '[']),
+    };
+
+    if is_json {
+        let value: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        // Only the matrix is needed, so skip deserializing the full document
+        let matrix = match value {
+            serde_json::Value::Object(mut doc) => match doc.remove("data") {
+                Some(matrix) => matrix,
+                None => return Err("JSON document has no design data".to_string()),
+            },
+            matrix => matrix,
+        };
+        let data: Vec<Vec<u32>> = serde_json::from_value(matrix)
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        Ok(("json", data))
+    } else {
+        let (_, data) = parse_design_table(&content, ',')?;
+        Ok(("csv", data))
+    }
+}
+
+/// Check an exported CSV or JSON file against its embedded checksum.
+///
+/// Returns whether the design data still matches the recorded hash, or an
+/// error if the file has no checksum.
 #[tauri::command]
-pub async fn export_csv(data: OAData, path: PathBuf) -> Result<(), String> {
-    let mut csv_content = String::new();
+pub async fn verify_file_integrity(path: PathBuf) -> Result<bool, String> {
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let (data, expected) = match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(value) => {
+            let expected = match value.get("checksum").and_then(|c| c.as_str()) {
+                Some(checksum) => checksum.to_string(),
+                None => return Err("File has no checksum".to_string()),
+            };
+            let data: Vec<Vec<u32>> = value
+                .get("data")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| format!("Failed to parse JSON: {}", e))?
+                .unwrap_or_default();
+            (data, expected)
+        }
+        Err(_) => {
+            let expected = match content
+                .lines()
+                .find_map(|line| line.strip_prefix(CSV_CHECKSUM_PREFIX))
+            {
+                Some(checksum) => checksum.trim().to_string(),
+                None => return Err("File has no checksum".to_string()),
+            };
+            // Data rows hold only digits and the delimiter; the header's
+            // quoted factor names may contain anything, even line breaks
+            let delimiter = content
+                .lines()
+                .take_while(|line| !line.starts_with(CSV_CHECKSUM_PREFIX))
+                .filter(|line| !line.trim().is_empty())
+                .last()
+                .and_then(|line| line.chars().find(|c| !c.is_ascii_digit() && *c != ' '))
+                .unwrap_or(',');
+            let records = split_records(&content, delimiter);
+
+            // Leading Run/DesignRow columns are not part of the design
+            let skip = records.first().map_or(0, |header| {
+                header
+                    .iter()
+                    .take_while(|h| matches!(h.trim(), "Run" | "DesignRow"))
+                    .count()
+            });
+            // Records that fail to parse (header, checksum line) are not design data
+            let data: Vec<Vec<u32>> = records
+                .iter()
+                .filter(|record| record.iter().any(|s| !s.trim().is_empty()))
+                .filter(|record| !record[0].starts_with(CSV_CHECKSUM_PREFIX))
+                .filter_map(|record| {
+                    record
+                        .iter()
+                        .skip(skip)
+                        .map(|s| s.trim().parse::<u32>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .ok()
+                })
+                .collect();
+            (data, expected)
+        }
+    };
+
+    Ok(design_checksum(&data) == expected)
+}
+
+fn write_analysis_xlsx(analysis: &DOEAnalysis, path: &Path) -> Result<(), XlsxError> {
+    let header = Format::new().set_bold();
+    let number = Format::new().set_num_format("0.0000");
+    let mut workbook = Workbook::new();
+
+    // Response table: level means per factor with delta and rank
+    let max_levels = analysis
+        .main_effects
+        .iter()
+        .map(|e| e.level_means.len())
+        .max()
+        .unwrap_or(0);
+    let mut headers = vec!["Factor".to_string()];
+    headers.extend((1..=max_levels).map(|level| format!("Level {}", level)));
+    headers.extend(["Delta".to_string(), "Rank".to_string()]);
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Response Table")?;
+    write_xlsx_header(sheet, &headers, &header)?;
+    for (i, effect) in analysis.main_effects.iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet.write_string(row, 0, &effect.factor_name)?;
+        for (level, mean) in effect.level_means.iter().enumerate() {
+            sheet.write_number_with_format(row, level as u16 + 1, *mean, &number)?;
+        }
+        sheet.write_number_with_format(row, max_levels as u16 + 1, effect.range, &number)?;
+        sheet.write_number(row, max_levels as u16 + 2, effect.rank as f64)?;
+    }
+    sheet.autofit();
+
+    // Main effects, one row per factor level
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Main Effects")?;
+    write_xlsx_header(sheet, &["Factor", "Level", "Mean", "Effect"], &header)?;
+    let mut row = 1;
+    for effect in &analysis.main_effects {
+        for (level, mean) in effect.level_means.iter().enumerate() {
+            let level_effect = effect.level_effects.get(level).copied().unwrap_or(0.0);
+            sheet.write_string(row, 0, &effect.factor_name)?;
+            sheet.write_number(row, 1, level as f64 + 1.0)?;
+            sheet.write_number_with_format(row, 2, *mean, &number)?;
+            sheet.write_number_with_format(row, 3, level_effect, &number)?;
+            row += 1;
+        }
+    }
+    sheet.autofit();
+
+    // S/N effects, one row per factor level
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("SN Effects")?;
+    write_xlsx_header(sheet, &["Factor", "Level", "SN Ratio (dB)", "Optimal"], &header)?;
+    let mut row = 1;
+    for effect in &analysis.sn_ratio_effects {
+        for (level, ratio) in effect.level_sn_ratios.iter().enumerate() {
+            sheet.write_string(row, 0, &effect.factor_name)?;
+            sheet.write_number(row, 1, level as f64 + 1.0)?;
+            sheet.write_number_with_format(row, 2, *ratio, &number)?;
+            if level == effect.optimal_level {
+                sheet.write_string(row, 3, "Yes")?;
+            }
+            row += 1;
+        }
+    }
+    sheet.autofit();
+
+    // ANOVA table with error and total rows
+    let anova = &analysis.anova;
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("ANOVA")?;
+    write_xlsx_header(
+        sheet,
+        &["Source", "SS", "DF", "MS", "F", "p", "Contribution %", "Pooled"],
+        &header,
+    )?;
+    let mut row = 1;
+    for entry in &anova.entries {
+        sheet.write_string(row, 0, &entry.factor_name)?;
+        sheet.write_number_with_format(row, 1, entry.sum_of_squares, &number)?;
+        sheet.write_number(row, 2, entry.degrees_of_freedom as f64)?;
+        sheet.write_number_with_format(row, 3, entry.mean_square, &number)?;
+        if let Some(f_ratio) = entry.f_ratio {
+            sheet.write_number_with_format(row, 4, f_ratio, &number)?;
+        }
+        if let Some(p_value) = entry.p_value {
+            sheet.write_number_with_format(row, 5, p_value, &number)?;
+        }
+        sheet.write_number_with_format(row, 6, entry.contribution_percent, &number)?;
+        if entry.pooled {
+            sheet.write_string(row, 7, "Yes")?;
+        }
+        row += 1;
+    }
+    sheet.write_string_with_format(row, 0, "Error", &header)?;
+    sheet.write_number_with_format(row, 1, anova.error_ss, &number)?;
+    sheet.write_number(row, 2, anova.error_df as f64)?;
+    sheet.write_number_with_format(row, 3, anova.error_ms, &number)?;
+    sheet.write_string_with_format(row + 1, 0, "Total", &header)?;
+    sheet.write_number_with_format(row + 1, 1, anova.total_ss, &number)?;
+    sheet.write_number(row + 1, 2, anova.total_df as f64)?;
+    sheet.autofit();
+
+    workbook.save(path)
+}
+
+fn write_design_xlsx(data: &OAData, path: &Path) -> Result<(), XlsxError> {
+    let header = Format::new().set_bold();
+    let mut workbook = Workbook::new();
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Array")?;
+    let headers: Vec<String> = (1..=data.factors).map(|i| format!("Factor{}", i)).collect();
+    write_xlsx_header(sheet, &headers, &header)?;
+    for (i, run) in data.data.iter().enumerate() {
+        for (col, &level) in run.iter().enumerate() {
+            sheet.write_number(i as u32 + 1, col as u16, level)?;
+        }
+    }
+    sheet.autofit();
+
+    let levels: Vec<String> = data.levels.iter().map(|l| l.to_string()).collect();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Metadata")?;
+    write_xlsx_header(sheet, &["Field", "Value"], &header)?;
+    sheet.write_string(1, 0, "id")?;
+    sheet.write_string(1, 1, &data.id)?;
+    sheet.write_string(2, 0, "runs")?;
+    sheet.write_number(2, 1, data.runs as f64)?;
+    sheet.write_string(3, 0, "factors")?;
+    sheet.write_number(3, 1, data.factors as f64)?;
+    sheet.write_string(4, 0, "levels")?;
+    sheet.write_string(4, 1, levels.join(", "))?;
+    sheet.write_string(5, 0, "strength")?;
+    sheet.write_number(5, 1, data.strength)?;
+    sheet.write_string(6, 0, "algorithm")?;
+    sheet.write_string(6, 1, &data.metadata.algorithm)?;
+    sheet.write_string(7, 0, "created_at")?;
+    sheet.write_string(7, 1, &data.metadata.created_at)?;
+    sheet.autofit();
+
+    workbook.save(path)
+}
+
+/// Write a bold header row and freeze it.
+fn write_xlsx_header<S: AsRef<str>>(
+    sheet: &mut Worksheet,
+    headers: &[S],
+    format: &Format,
+) -> Result<(), XlsxError> {
+    for (col, title) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, title.as_ref(), format)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
 
+/// Columns and trailers to include in CSV output.
+struct CsvOptions {
+    delimiter: char,
+    factor_names: Option<Vec<String>>,
+    level_labels: Option<Vec<Vec<String>>>,
+    include_header: bool,
+    include_run_column: bool,
+    include_design_row: bool,
+    include_checksum: bool,
+}
+
+/// Write design rows as CSV in the given order, shared by the CSV exports.
+fn write_csv<W: Write>(
+    writer: &mut W,
+    data: &OAData,
+    order: &[usize],
+    options: &CsvOptions,
+) -> std::io::Result<()> {
+    let delimiter = options.delimiter.to_string();
     // Header row
-    let headers: Vec<String> = (1..=data.factors)
-        .map(|i| format!("Factor{}", i))
-        .collect();
-    csv_content.push_str(&headers.join(","));
-    csv_content.push('\n');
+    if options.include_header {
+        let mut headers: Vec<String> = Vec::new();
+        if options.include_run_column {
+            headers.push("Run".to_string());
+            if options.include_design_row {
+                headers.push("DesignRow".to_string());
+            }
+        }
+        match &options.factor_names {
+            Some(names) if names.len() == data.factors => headers.extend(
+                names
+                    .iter()
+                    .map(|name| escape_delimited_field(name, options.delimiter)),
+            ),
+            _ => headers.extend((1..=data.factors).map(|i| format!("Factor{}", i))),
+        }
+        writeln!(writer, "{}", headers.join(&delimiter))?;
+    }
 
     // Data rows
-    for row in &data.data {
-        let row_str: Vec<String> = row.iter().map(|v| v.to_string()).collect();
-        csv_content.push_str(&row_str.join(","));
-        csv_content.push('\n');
+    let mut hasher = options.include_checksum.then(Sha256::new);
+    for (run, &design_row) in order.iter().enumerate() {
+        let codes: Vec<String> = data.data[design_row].iter().map(|v| v.to_string()).collect();
+        // The checksum is always over comma-separated codes, whatever the
+        // delimiter
+        if let Some(hasher) = &mut hasher {
+            hash_row(hasher, &codes.join(","));
+        }
+
+        if options.include_run_column {
+            write!(writer, "{}{}", run + 1, delimiter)?;
+            if options.include_design_row {
+                write!(writer, "{}{}", design_row + 1, delimiter)?;
+            }
+        }
+        let cells: Vec<String> = data.data[design_row]
+            .iter()
+            .zip(codes)
+            .enumerate()
+            .map(|(col, (&level, code))| {
+                match level_label(options.level_labels.as_deref(), col, level) {
+                    Some(label) => escape_delimited_field(label, options.delimiter),
+                    None => code,
+                }
+            })
+            .collect();
+        writeln!(writer, "{}", cells.join(&delimiter))?;
     }
 
-    std::fs::write(&path, csv_content).map_err(|e| format!("Failed to write CSV: {}", e))?;
+    if let Some(hasher) = hasher {
+        writeln!(writer, "{}{:x}", CSV_CHECKSUM_PREFIX, hasher.finalize())?;
+    }
 
     Ok(())
 }
 
-/// Export an array to JSON format.
-#[tauri::command]
-pub async fn export_json(data: OAData, path: PathBuf) -> Result<(), String> {
-    let json = serde_json::to_string_pretty(&data)
-        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+/// The display label for a coded level, if one is given.
+fn level_label(labels: Option<&[Vec<String>]>, col: usize, level: u32) -> Option<&str> {
+    labels?.get(col)?.get(level as usize).map(|label| label.as_str())
+}
 
-    std::fs::write(&path, json).map_err(|e| format!("Failed to write JSON: {}", e))?;
+/// Resolve a CSV delimiter, defaulting to a comma.
+///
+/// Digits, line breaks and the `"` quote character would be ambiguous with
+/// the data or its quoting, so are rejected.
+fn csv_delimiter(delimiter: Option<char>) -> Result<char, String> {
+    match delimiter {
+        None => Ok(','),
+        Some(d) if d.is_ascii_digit() || matches!(d, '\n' | '\r' | '"') => {
+            Err(format!("Invalid CSV delimiter {:?}", d))
+        }
+        Some(d) => Ok(d),
+    }
+}
 
-    Ok(())
+/// Read a text file, transparently decompressing it if it is gzipped.
+///
+/// Gzip is detected by a `.gz` extension or the gzip magic number.
+fn read_import_file(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let is_gzip = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+        || bytes.starts_with(&[0x1f, 0x8b]);
+    if !is_gzip {
+        return String::from_utf8(bytes).map_err(|e| format!("Failed to read file: {}", e));
+    }
+
+    let mut content = String::new();
+    GzDecoder::new(bytes.as_slice())
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to decompress file: {}", e))?;
+    Ok(content)
 }
 
-/// Export an array to LaTeX tabular format.
-#[tauri::command]
-pub fn export_latex(data: OAData) -> Result<String, String> {
-    let mut latex = String::new();
+/// Lowercase file extension, looking through a trailing `.gz`.
+fn import_extension(path: &Path) -> String {
+    let extension = |p: &Path| {
+        p.extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
 
-    // Begin tabular environment
-    let col_spec = format!("|{}|", "c|".repeat(data.factors));
-    latex.push_str(&format!("\\begin{{tabular}}{{{}}}\n", col_spec));
-    latex.push_str("\\hline\n");
+    let outer = extension(path);
+    if outer == "gz" {
+        extension(&path.with_extension(""))
+    } else {
+        outer
+    }
+}
 
-    // Header row
-    let headers: Vec<String> = (1..=data.factors)
-        .map(|i| format!("$F_{{{}}}$", i))
-        .collect();
-    latex.push_str(&headers.join(" & "));
-    latex.push_str(" \\\\\n\\hline\n");
+/// Tab-delimited JMP table with header and column-properties comments.
+///
+/// Comment lines start with `//`; JMP's text import skips them when told the
+/// column names are on the first non-comment line.
+fn jmp_table(data: &OAData) -> String {
+    let name = data.metadata.name.as_deref().unwrap_or("Design");
 
-    // Data rows
+    let mut table = String::new();
+    table.push_str(&format!("// JMP Data Table: {} {}\n", name, oa_notation(data)));
+    table.push_str("// Column Properties:\n");
+    for col in 0..data.factors {
+        let levels = data.levels.get(col).map_or(String::new(), |l| format!(", Levels({})", l));
+        table.push_str(&format!(
+            "//   Factor{}: Numeric, Modeling Type(Nominal){}\n",
+            col + 1,
+            levels
+        ));
+    }
+
+    let headers: Vec<String> = (1..=data.factors).map(|i| format!("Factor{}", i)).collect();
+    table.push_str(&headers.join("\t"));
+    table.push('\n');
     for row in &data.data {
         let row_str: Vec<String> = row.iter().map(|v| v.to_string()).collect();
-        latex.push_str(&row_str.join(" & "));
-        latex.push_str(" \\\\\n");
+        table.push_str(&row_str.join("\t"));
+        table.push('\n');
     }
 
-    latex.push_str("\\hline\n");
-    latex.push_str("\\end{tabular}\n");
+    table
+}
 
-    // Add caption with array notation
-    let levels = if data.levels.len() == 1 {
-        data.levels[0].to_string()
-    } else {
-        format!("({})", data.levels.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(","))
-    };
+/// JSL script creating a data table with one Nominal column per factor.
+fn jmp_script(data: &OAData) -> String {
+    // JSL escapes a double quote inside a string as \!"
+    let name = data.metadata.name.as_deref().unwrap_or("Design").replace('"', "\\!\"");
 
-    latex.push_str(&format!(
-        "\n% OA({}, {}, {}, {})\n",
-        data.runs, data.factors, levels, data.strength
+    let mut script = String::new();
+    script.push_str(&format!(
+        "// OA({}, {}, {:?}, {})\n",
+        data.runs, data.factors, data.levels, data.strength
     ));
+    script.push_str(&format!("New Table( \"{}\",\n", name));
+    script.push_str(&format!("\tAdd Rows( {} ),\n", data.data.len()));
 
-    if let Some(name) = &data.metadata.name {
-        latex.push_str(&format!("% Name: {}\n", name));
-    }
-    latex.push_str(&format!("% Algorithm: {}\n", data.metadata.algorithm));
+    let columns: Vec<String> = (0..data.factors)
+        .map(|col| {
+            let values: Vec<String> = data
+                .data
+                .iter()
+                .map(|row| row.get(col).map_or(".".to_string(), |v| v.to_string()))
+                .collect();
+            format!(
+                "\tNew Column( \"Factor{}\", Numeric, \"Nominal\", Format( \"Best\", 12 ),\n\t\tSet Values( [{}] )\n\t)",
+                col + 1,
+                values.join(", ")
+            )
+        })
+        .collect();
+    script.push_str(&columns.join(",\n"));
+    script.push_str("\n);\n");
 
-    Ok(latex)
+    script
 }
 
-/// Import an array from CSV file.
-#[tauri::command]
-pub async fn import_csv(path: PathBuf) -> Result<Vec<Vec<u32>>, String> {
-    let content =
-        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+/// Header names (if the file has a header row) and design rows.
+type DesignTable = (Option<Vec<String>>, Vec<Vec<u32>>);
 
+/// Parse delimited design data, returning the header row's names if there is one.
+///
+/// The first line is a header if it does not parse as numbers. Blank lines and
+/// checksum lines are skipped.
+fn parse_design_table(content: &str, delimiter: char) -> Result<DesignTable, String> {
+    let mut header = None;
     let mut data: Vec<Vec<u32>> = Vec::new();
-    let mut lines = content.lines();
+    let mut records = split_records(content, delimiter).into_iter();
 
-    // Skip header if present (check if first line contains non-numeric values)
-    if let Some(first_line) = lines.next() {
-        let first_row: Result<Vec<u32>, _> = first_line
-            .split(',')
-            .map(|s| s.trim().parse::<u32>())
-            .collect();
+    // Capture header if present (check if first record contains non-numeric values)
+    if let Some(first_record) = records.next() {
+        let first_row: Result<Vec<u32>, _> =
+            first_record.iter().map(|s| s.trim().parse::<u32>()).collect();
 
         match first_row {
             Ok(row) => data.push(row),
-            Err(_) => {} // Skip header row
+            Err(_) => header = Some(first_record.iter().map(|s| s.trim().to_string()).collect()),
         }
     }
 
     // Parse remaining rows
-    for line in lines {
-        if line.trim().is_empty() {
+    for record in records {
+        if record.iter().all(|s| s.trim().is_empty()) || record[0].starts_with(CSV_CHECKSUM_PREFIX)
+        {
             continue;
         }
 
-        let row: Vec<u32> = line
-            .split(',')
+        let row: Vec<u32> = record
+            .iter()
             .map(|s| {
                 s.trim()
                     .parse::<u32>()
@@ -137,61 +1961,149 @@ pub async fn import_csv(path: PathBuf) -> Result<Vec<Vec<u32>>, String> {
         return Err("No data found in file".to_string());
     }
 
-    Ok(data)
+    Ok((header, data))
 }
 
-/// Import an array from JSON file.
-#[tauri::command]
-pub async fn import_json(path: PathBuf) -> Result<OAData, String> {
-    let content =
-        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+/// Split delimited text into records of fields, per RFC 4180.
+///
+/// Quoted fields may contain the delimiter, line breaks, and doubled `""`
+/// quotes. Records end at `\n` or `\r\n`.
+fn split_records(content: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
 
-    serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                field.push('"');
+                chars.next();
+            } else {
+                in_quotes = false;
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' || (c == '\r' && chars.peek() == Some(&'\n')) {
+            if c == '\r' {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            records.push(std::mem::take(&mut record));
+        } else {
+            field.push(c);
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
 }
 
-/// Validate imported array data.
-#[tauri::command]
-pub fn validate_import(data: Vec<Vec<u32>>) -> Result<crate::types::ImportValidation, String> {
-    if data.is_empty() {
-        return Err("Array data is empty".to_string());
+/// Read design data from a workbook sheet (the first one if none is named).
+///
+/// Mirrors `parse_design_table`: a first row that is not all numbers is a
+/// header. Trailing empty cells are trimmed from each row and trailing empty
+/// rows are dropped before row lengths are compared.
+fn read_design_sheet(path: &Path, sheet: Option<&str>) -> Result<Vec<Vec<u32>>, String> {
+    let mut workbook =
+        open_workbook_auto(path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let range = match sheet {
+        Some(name) => workbook
+            .worksheet_range(name)
+            .map_err(|e| format!("Failed to read sheet '{}': {}", name, e))?,
+        None => match workbook.worksheet_range_at(0) {
+            Some(range) => range.map_err(|e| format!("Failed to read sheet: {}", e))?,
+            None => return Err("Workbook has no sheets".to_string()),
+        },
+    };
+
+    let mut rows: Vec<&[Data]> = range
+        .rows()
+        .map(|row| {
+            let len = row.iter().rposition(|cell| *cell != Data::Empty).map_or(0, |i| i + 1);
+            &row[..len]
+        })
+        .collect();
+    while rows.last().is_some_and(|row| row.is_empty()) {
+        rows.pop();
     }
 
-    let runs = data.len();
-    let factors = data[0].len();
+    let mut data: Vec<Vec<u32>> = Vec::new();
+    for (idx, cells) in rows.into_iter().enumerate() {
+        // Skip header row (first row is not all numbers)
+        if idx == 0 && cells.iter().any(|cell| sheet_level(cell).is_err()) {
+            continue;
+        }
 
-    // Check all rows have same length
-    for (i, row) in data.iter().enumerate() {
-        if row.len() != factors {
+        let row = cells.iter().map(sheet_level).collect::<Result<Vec<_>, _>>()?;
+        if !data.is_empty() && row.len() != data[0].len() {
             return Err(format!(
-                "Row {} has {} columns, expected {}",
-                i + 1,
-                row.len(),
-                factors
+                "Inconsistent row length: expected {}, got {}",
+                data[0].len(),
+                row.len()
             ));
         }
+
+        data.push(row);
     }
 
-    // Detect levels per factor
-    let mut levels = vec![0u32; factors];
-    for col in 0..factors {
-        let max_val = data.iter().map(|row| row[col]).max().unwrap_or(0);
-        levels[col] = max_val + 1;
+    if data.is_empty() {
+        return Err("No data found in file".to_string());
     }
 
-    // Check for consistent levels (pure vs mixed)
-    let is_mixed = levels.iter().collect::<std::collections::HashSet<_>>().len() > 1;
+    Ok(data)
+}
 
-    // Estimate strength by checking balance
-    let estimated_strength = estimate_strength(&data, &levels);
+/// Convert a worksheet cell to a level, accepting whole numbers only.
+fn sheet_level(cell: &Data) -> Result<u32, String> {
+    let level = match cell {
+        Data::Int(value) => u32::try_from(*value).ok(),
+        Data::Float(value) if value.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(value) => {
+            Some(*value as u32)
+        }
+        Data::String(text) => text.trim().parse::<u32>().ok(),
+        _ => None,
+    };
 
-    Ok(crate::types::ImportValidation {
-        runs,
-        factors,
-        levels: levels.clone(),
-        is_mixed,
-        estimated_strength,
-        warnings: generate_warnings(&data, &levels),
-    })
+    match level {
+        Some(level) => Ok(level),
+        None => Err(format!("Invalid value '{}': expected a non-negative integer", cell)),
+    }
+}
+
+/// Check that every (0-based) run index is below `runs`.
+fn validate_row_indices(rows: &[usize], runs: usize) -> Result<(), String> {
+    match rows.iter().find(|&&r| r >= runs) {
+        Some(&row) => Err(format!(
+            "Run index {} is out of range (array has {} runs)",
+            row, runs
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Keep only the given (0-based) run indices, in the order given.
+fn select_rows(mut data: OAData, rows: Option<Vec<usize>>) -> Result<OAData, String> {
+    let rows = match rows {
+        Some(rows) => rows,
+        None => return Ok(data),
+    };
+
+    validate_row_indices(&rows, data.data.len())?;
+
+    data.data = rows.iter().map(|&r| data.data[r].clone()).collect();
+    data.runs = data.data.len();
+    Ok(data)
 }
 
 fn estimate_strength(data: &[Vec<u32>], levels: &[u32]) -> u32 {
@@ -239,3 +2151,306 @@ fn generate_warnings(data: &[Vec<u32>], levels: &[u32]) -> Vec<String> {
 
     warnings
 }
+
+/// Rows where every factor sits at its central level.
+///
+/// Only factors with an odd number of levels have a central level, so any
+/// even-level factor rules out center points. A 3-level OA such as L9 already
+/// contains an all-middle row as an ordinary run, so an all-middle row only
+/// counts when the remaining rows never use a middle level (a two-level design
+/// augmented with centre runs) or when it is repeated.
+pub(crate) fn find_center_points(data: &[Vec<u32>], levels: &[u32]) -> Vec<usize> {
+    if levels.iter().any(|&l| l % 2 == 0) {
+        return Vec::new();
+    }
+
+    let is_center = |row: &Vec<u32>| row.iter().zip(levels).all(|(&v, &l)| v == (l - 1) / 2);
+    let (center, base): (Vec<(usize, &Vec<u32>)>, Vec<_>) =
+        data.iter().enumerate().partition(|(_, row)| is_center(row));
+
+    let base_uses_middle = base
+        .iter()
+        .any(|(_, row)| row.iter().zip(levels).any(|(&v, &l)| v == (l - 1) / 2));
+    if base_uses_middle && center.len() < 2 {
+        return Vec::new();
+    }
+
+    center.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Group columns that are identical up to a relabeling of their levels.
+fn find_duplicate_columns(data: &[Vec<u32>], factors: usize) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(Vec<u32>, Vec<usize>)> = Vec::new();
+
+    for col in 0..factors {
+        // Relabel levels in order of first appearance
+        let mut labels = std::collections::HashMap::new();
+        let pattern: Vec<u32> = data
+            .iter()
+            .map(|row| {
+                let next = labels.len() as u32;
+                *labels.entry(row[col]).or_insert(next)
+            })
+            .collect();
+
+        match groups.iter_mut().find(|(p, _)| *p == pattern) {
+            Some((_, cols)) => cols.push(col),
+            None => groups.push((pattern, vec![col])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, cols)| cols)
+        .filter(|cols| cols.len() > 1)
+        .collect()
+}
+
+/// SHA-256 over the design rows, one comma-separated row per line, as lowercase hex.
+fn design_checksum(data: &[Vec<u32>]) -> String {
+    let mut hasher = Sha256::new();
+    for row in data {
+        let row_str: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+        hash_row(&mut hasher, &row_str.join(","));
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Add one comma-separated design row to a checksum.
+fn hash_row(hasher: &mut Sha256, row: &str) {
+    hasher.update(row.as_bytes());
+    hasher.update(b"\n");
+}
+
+/// Check every row has one column per factor, as the table exports assume.
+fn check_row_widths(data: &OAData) -> Result<(), String> {
+    match data.data.iter().position(|row| row.len() != data.factors) {
+        Some(run) => Err(format!(
+            "Row {} has {} columns, expected {}",
+            run + 1,
+            data.data[run].len(),
+            data.factors
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `OA(runs, factors, levels, strength)`, with per-factor levels in parentheses.
+fn oa_notation(data: &OAData) -> String {
+    let levels = if data.levels.len() == 1 {
+        data.levels[0].to_string()
+    } else {
+        format!("({})", data.levels.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(","))
+    };
+
+    format!("OA({}, {}, {}, {})", data.runs, data.factors, levels, data.strength)
+}
+
+fn format_float(value: f64, precision: usize) -> String {
+    format!("{:.*}", precision, value)
+}
+
+/// Compare the stored level counts with the distinct values actually present.
+fn level_mismatch_warnings(data: &OAData) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let factors = data.data.first().map(|row| row.len()).unwrap_or(data.factors);
+
+    if data.levels.len() != 1 && data.levels.len() != factors {
+        warnings.push(format!(
+            "Level list has {} entries but the array has {} factors",
+            data.levels.len(),
+            factors
+        ));
+        return warnings;
+    }
+
+    for col in 0..factors {
+        let stored = if data.levels.len() == 1 { data.levels[0] } else { data.levels[col] };
+        let values: std::collections::BTreeSet<u32> =
+            data.data.iter().filter_map(|row| row.get(col).copied()).collect();
+        let max_value = values.iter().next_back().copied().unwrap_or(0);
+
+        if values.len() != stored as usize {
+            warnings.push(format!(
+                "Factor {} is stored with {} levels but has {} distinct values",
+                col + 1,
+                stored,
+                values.len()
+            ));
+        } else if max_value >= stored {
+            warnings.push(format!(
+                "Factor {} has value {}, outside the coded range 0..{}",
+                col + 1,
+                max_value,
+                stored
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Escape LaTeX special characters in text.
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape HTML special characters in text and attribute values.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_csv_field(field: &str) -> String {
+    escape_delimited_field(field, ',')
+}
+
+/// Quote a field per RFC 4180 if it contains the delimiter, quotes, or newlines.
+fn escape_delimited_field(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OAMetadata;
+
+    fn full_factorial_2x2() -> OAData {
+        OAData {
+            id: "test".to_string(),
+            runs: 4,
+            factors: 2,
+            levels: vec![2, 2],
+            strength: 2,
+            data: vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]],
+            metadata: OAMetadata {
+                name: None,
+                algorithm: "test".to_string(),
+                created_at: String::new(),
+                notes: None,
+            },
+            warnings: vec![],
+        }
+    }
+
+    /// Drive a command future that never waits on I/O readiness to completion.
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        loop {
+            if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn csv_round_trip_keeps_quoted_factor_names() {
+        let data = full_factorial_2x2();
+        let options = CsvOptions {
+            delimiter: ',',
+            factor_names: Some(vec!["Temp, °C".to_string(), "Time".to_string()]),
+            level_labels: None,
+            include_header: true,
+            include_run_column: false,
+            include_design_row: false,
+            include_checksum: false,
+        };
+
+        let mut buffer = Vec::new();
+        write_csv(&mut buffer, &data, &[0, 1, 2, 3], &options).unwrap();
+        let content = String::from_utf8(buffer).unwrap();
+        let (header, rows) = parse_design_table(&content, ',').unwrap();
+
+        assert_eq!(header, Some(vec!["Temp, °C".to_string(), "Time".to_string()]));
+        assert_eq!(rows, data.data);
+    }
+
+    #[test]
+    fn l9_middle_row_is_not_a_center_point() {
+        let l9 = vec![
+            vec![0, 0, 0, 0],
+            vec![0, 1, 1, 1],
+            vec![0, 2, 2, 2],
+            vec![1, 0, 1, 2],
+            vec![1, 1, 2, 0],
+            vec![1, 2, 0, 1],
+            vec![2, 0, 2, 1],
+            vec![2, 1, 0, 2],
+            vec![2, 2, 1, 0],
+        ];
+        assert!(find_center_points(&l9, &[3, 3, 3, 3]).is_empty());
+
+        let mut repeated = l9.clone();
+        repeated.push(vec![1, 1, 1, 1]);
+        repeated.push(vec![1, 1, 1, 1]);
+        assert_eq!(find_center_points(&repeated, &[3, 3, 3, 3]), vec![9, 10]);
+    }
+
+    #[test]
+    fn two_level_design_with_center_runs() {
+        let data = vec![vec![0, 0], vec![0, 2], vec![2, 0], vec![2, 2], vec![1, 1]];
+        assert_eq!(find_center_points(&data, &[3, 3]), vec![4]);
+    }
+
+    #[test]
+    fn design_checksum_hashes_one_line_per_row() {
+        // sha256 of "0,0\n0,1\n1,0\n1,1\n"
+        assert_eq!(
+            design_checksum(&full_factorial_2x2().data),
+            "5672bddda3b3f6cf663cd5523dc92b8ff13d63b66ee418fbf9b4c2fa4ff59d5f"
+        );
+    }
+
+    #[test]
+    fn checksum_detects_a_tampered_csv() {
+        let path = std::env::temp_dir().join(format!("taguchi-checksum-{}.csv", std::process::id()));
+        block_on(export_csv(
+            full_factorial_2x2(),
+            path.clone(),
+            None,
+            Some(true),
+            Some(true),
+            Some('\t'),
+            Some(vec!["Temp".to_string(), "Time".to_string()]),
+            None,
+        ))
+        .unwrap();
+        assert!(block_on(verify_file_integrity(path.clone())).unwrap());
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let tampered = content.replacen("2\t0\t1", "2\t1\t1", 1);
+        assert_ne!(tampered, content);
+        std::fs::write(&path, tampered).unwrap();
+        let intact = block_on(verify_file_integrity(path.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!intact);
+    }
+}
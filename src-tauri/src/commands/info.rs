@@ -0,0 +1,28 @@
+//! Backend version and capability introspection.
+
+use crate::types::LibraryInfo;
+
+/// Version of the data formats written by export commands.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Construction algorithms supported by the linked taguchi library.
+const SUPPORTED_CONSTRUCTIONS: &[&str] = &[
+    "Bose",
+    "Bush",
+    "BoseBush",
+    "AddelmanKempthorne",
+    "HadamardSylvester",
+    "HadamardPaley",
+    "RaoHamming",
+];
+
+/// Get version and capability information about the backend.
+#[tauri::command]
+pub fn get_library_info() -> LibraryInfo {
+    LibraryInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        library_version: env!("TAGUCHI_VERSION").to_string(),
+        schema_version: SCHEMA_VERSION,
+        constructions: SUPPORTED_CONSTRUCTIONS.iter().map(|c| c.to_string()).collect(),
+    }
+}
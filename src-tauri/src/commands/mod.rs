@@ -6,15 +6,21 @@
 //! - `analysis`: Verification and statistics
 //! - `export`: Import/export functionality
 //! - `doe_analysis`: DOE statistical analysis
+//! - `info`: Version and capability introspection
+//! - `planning`: Power, sample-size, DOF, and budget estimates
 
 pub mod analysis;
 pub mod builder;
 pub mod catalogue;
 pub mod doe_analysis;
 pub mod export;
+pub mod info;
+pub mod planning;
 
 pub use analysis::*;
 pub use builder::*;
 pub use catalogue::*;
 pub use doe_analysis::*;
 pub use export::*;
+pub use info::*;
+pub use planning::*;
@@ -9,26 +9,92 @@ mod types;
 use commands::{
     // Builder commands
     build_oa,
+    build_with_construction,
     get_available_constructions,
+    get_available_constructions_mixed,
+    mirror_design,
+    reorder_columns,
+    split_design,
     validate_build_params,
     // Catalogue commands
+    get_array_by_size,
     get_standard_array,
+    identify_standard_array,
     list_standard_arrays,
     search_catalogue,
     // Analysis commands
+    canonicalize,
+    classify_design,
     compute_array_strength,
+    diff_designs,
+    get_array_quality_card,
     get_balance_report,
     get_correlation_matrix,
+    get_generalized_resolution,
+    get_information_matrix,
+    list_covered_tuples,
+    max_factors_for_strength,
     verify_array,
     // DOE Analysis commands
+    compute_main_effects_raw,
+    curvature_test,
+    factor_marginal_means,
+    get_effect_standard_errors,
+    get_effects_plot_bands,
+    get_factor_importance,
+    get_interaction_plot_data,
+    get_polynomial_contrasts,
+    get_response_table,
+    get_run_sn_contributions,
+    precheck_for_analysis,
     run_doe_analysis,
+    sn_for_subset,
+    suggest_pooling,
+    validate_doe_setup,
+    variance_components,
     // Export/Import commands
+    export_analysis_csv,
+    export_analysis_json,
+    export_analysis_report,
+    export_analysis_xlsx,
     export_csv,
+    export_csv_string,
+    export_designs_json,
+    export_html,
+    export_jmp,
     export_json,
     export_latex,
+    export_markdown,
+    export_python,
+    export_r_script,
+    export_response_table,
+    export_run_sheet,
+    export_sql,
+    export_tsv,
+    export_xlsx,
+    flatten_responses,
+    import_analysis_json,
     import_csv,
+    import_csv_with_headers,
+    import_designs_json,
     import_json,
+    import_run_sheet,
+    import_tsv,
+    import_xlsx,
+    load_project,
+    merge_replicate_files,
+    preview_import,
+    save_project,
+    transpose_design,
     validate_import,
+    verify_file_integrity,
+    // Info commands
+    get_library_info,
+    // Planning commands
+    dof_budget,
+    estimate_experiment,
+    estimate_power,
+    required_replicates,
 };
 
 /// Run the Tauri application.
@@ -42,26 +108,92 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // Builder commands
             build_oa,
+            build_with_construction,
             get_available_constructions,
+            get_available_constructions_mixed,
             validate_build_params,
+            reorder_columns,
+            mirror_design,
+            split_design,
             // Catalogue commands
             list_standard_arrays,
             get_standard_array,
             search_catalogue,
+            identify_standard_array,
+            get_array_by_size,
             // Analysis commands
             verify_array,
             compute_array_strength,
             get_balance_report,
             get_correlation_matrix,
+            list_covered_tuples,
+            classify_design,
+            max_factors_for_strength,
+            diff_designs,
+            canonicalize,
+            get_array_quality_card,
+            get_information_matrix,
+            get_generalized_resolution,
             // DOE Analysis commands
             run_doe_analysis,
+            suggest_pooling,
+            get_interaction_plot_data,
+            get_response_table,
+            sn_for_subset,
+            compute_main_effects_raw,
+            curvature_test,
+            validate_doe_setup,
+            factor_marginal_means,
+            get_run_sn_contributions,
+            get_effect_standard_errors,
+            get_effects_plot_bands,
+            precheck_for_analysis,
+            variance_components,
+            get_factor_importance,
+            get_polynomial_contrasts,
             // Export/Import commands
             export_csv,
             export_json,
             export_latex,
+            export_sql,
+            export_run_sheet,
+            export_analysis_json,
+            export_analysis_csv,
+            export_analysis_report,
+            export_analysis_xlsx,
             import_csv,
             import_json,
+            import_analysis_json,
+            import_run_sheet,
+            merge_replicate_files,
+            transpose_design,
+            flatten_responses,
+            export_response_table,
             validate_import,
+            verify_file_integrity,
+            export_designs_json,
+            import_designs_json,
+            import_csv_with_headers,
+            export_jmp,
+            preview_import,
+            save_project,
+            load_project,
+            export_xlsx,
+            import_xlsx,
+            export_markdown,
+            export_html,
+            export_tsv,
+            import_tsv,
+            export_csv_string,
+            export_r_script,
+            export_python,
+            // Info commands
+            get_library_info,
+            // Planning commands
+            estimate_power,
+            required_replicates,
+            estimate_experiment,
+            dof_budget,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -24,6 +24,9 @@ pub struct OAData {
     pub data: Vec<Vec<u32>>,
     /// Metadata about the array.
     pub metadata: OAMetadata,
+    /// Non-fatal warnings from building or importing the array.
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 /// Metadata for an orthogonal array.
@@ -41,21 +44,25 @@ pub struct OAMetadata {
 }
 
 /// Request to build an orthogonal array.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildRequest {
     /// Number of levels - either a single value (symmetric) or array (mixed).
     pub levels: LevelSpec,
     /// Number of factors.
     pub factors: usize,
-    /// Desired strength.
+    /// Desired strength, or the minimum strength when `max_strength` is set.
     pub strength: u32,
+    /// Optional highest strength to try; the strongest array within the run budget is built.
+    pub max_strength: Option<u32>,
     /// Optional minimum runs constraint.
     pub min_runs: Option<usize>,
+    /// Build mode: "orthogonal" (default) or "covering".
+    pub mode: Option<String>,
 }
 
 /// Level specification - symmetric or mixed.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
 #[serde(untagged)]
 pub enum LevelSpec {
     /// Same number of levels for all factors.
@@ -74,6 +81,8 @@ pub struct ConstructionOption {
     pub runs: usize,
     /// Maximum number of factors supported.
     pub max_factors: usize,
+    /// Runs relative to the smallest option returned (1.0 = smallest).
+    pub relative_size: f64,
     /// Human-readable description.
     pub description: String,
     /// Any constraints or requirements.
@@ -94,6 +103,39 @@ pub struct ValidationResult {
     pub suggestions: Vec<ConstructionOption>,
 }
 
+/// Build failure with the closest feasible alternatives.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildError {
+    /// Error message, listing the closest alternatives if any.
+    pub message: String,
+    /// Closest constructions that can be built, best first.
+    pub alternatives: Vec<BuildAlternative>,
+}
+
+impl From<String> for BuildError {
+    fn from(message: String) -> Self {
+        BuildError {
+            message,
+            alternatives: vec![],
+        }
+    }
+}
+
+/// A construction that could be built instead of a failed request.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildAlternative {
+    /// Name of the construction algorithm.
+    pub name: String,
+    /// Number of runs it produces.
+    pub runs: usize,
+    /// Maximum number of factors it supports.
+    pub max_factors: usize,
+    /// Strength of the arrays it produces.
+    pub strength: u32,
+}
+
 /// Verification result from checking array strength.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -142,6 +184,8 @@ pub struct BalanceData {
     pub level_counts: Vec<HashMap<u32, usize>>,
     /// Expected count per level.
     pub expected_count: usize,
+    /// Shannon entropy of each factor's level distribution, normalized so 1.0 is uniform.
+    pub entropy: Vec<f64>,
 }
 
 /// Correlation data between factors.
@@ -154,6 +198,84 @@ pub struct CorrelationData {
     pub factors: usize,
 }
 
+/// Kind of experimental design an array represents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DesignType {
+    /// Every level combination appears exactly once.
+    FullFactorial,
+    /// A symmetric subset of the full factorial.
+    FractionalFactorial,
+    /// Two-level design with runs a multiple of 4 but not a power of 2.
+    PlackettBurman,
+    /// Orthogonal array with differing levels per factor.
+    MixedOrthogonalArray,
+}
+
+/// Classification of an array's design type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignClassification {
+    /// The detected design type.
+    pub design_type: DesignType,
+    /// Fraction of the full factorial (e.g., "1/8 fraction"), if computable.
+    pub fraction: Option<String>,
+    /// Runs in the corresponding full factorial, if it fits in 64 bits.
+    pub full_factorial_runs: Option<u64>,
+    /// Human-readable description.
+    pub description: String,
+}
+
+/// Cell-level differences between two arrays of the same shape.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignDiff {
+    /// Number of runs in both arrays.
+    pub runs: usize,
+    /// Number of factors in both arrays.
+    pub factors: usize,
+    /// Whether the arrays are identical.
+    pub identical: bool,
+    /// Cells that differ.
+    pub differences: Vec<CellDifference>,
+}
+
+/// A single differing cell.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CellDifference {
+    /// Row index.
+    pub row: usize,
+    /// Column index.
+    pub col: usize,
+    /// Value in the first array.
+    pub old: u32,
+    /// Value in the second array.
+    pub new: u32,
+}
+
+/// At-a-glance quality summary for an array.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityCard {
+    /// Number of runs.
+    pub runs: usize,
+    /// Number of factors.
+    pub factors: usize,
+    /// Actual strength, checked up to 4 (fully for two-level arrays).
+    pub strength: u32,
+    /// Whether every factor is balanced.
+    pub is_balanced: bool,
+    /// Indices of factors whose levels are not balanced.
+    pub unbalanced_factors: Vec<usize>,
+    /// Largest absolute correlation between two distinct factors.
+    pub max_abs_correlation: f64,
+    /// Root mean square of the correlations between distinct factors (0 = orthogonal).
+    pub orthogonality_defect: f64,
+    /// Resolution for two-level arrays (strength + 1); None otherwise or for full factorials.
+    pub resolution: Option<u32>,
+}
+
 /// Information about a standard (catalogue) array.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -172,6 +294,64 @@ pub struct StandardArrayInfo {
     pub description: String,
 }
 
+/// Structured import failure pointing at the offending spot in the file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportError {
+    /// 1-based line of the error, if known.
+    pub line: Option<usize>,
+    /// 1-based column of the error, if known.
+    pub column: Option<usize>,
+    /// Error message, including the failing field where available.
+    pub message: String,
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(e: serde_json::Error) -> Self {
+        // serde_json reports line 0 for errors without a position
+        let position = (e.line() > 0).then(|| (e.line(), e.column()));
+        ImportError {
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            message: format!("Failed to parse JSON: {}", e),
+        }
+    }
+}
+
+impl From<String> for ImportError {
+    fn from(message: String) -> Self {
+        ImportError {
+            line: None,
+            column: None,
+            message,
+        }
+    }
+}
+
+/// Table style for LaTeX export.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LatexStyle {
+    /// `\hline` rules and vertical bars between columns.
+    #[default]
+    #[serde(alias = "classic")]
+    Grid,
+    /// `\toprule`/`\midrule`/`\bottomrule`, no vertical rules, in a captioned
+    /// table (needs `\usepackage{booktabs}`).
+    Booktabs,
+}
+
+/// Output format for a run sheet.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunSheetFormat {
+    /// Comma-separated values, for spreadsheets.
+    #[default]
+    Csv,
+    /// A standalone HTML table, for printing.
+    Html,
+}
+
 /// Validation result for imported array data.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -186,10 +366,64 @@ pub struct ImportValidation {
     pub is_mixed: bool,
     /// Estimated strength.
     pub estimated_strength: u32,
+    /// Groups of columns that are identical up to level relabeling.
+    pub duplicate_columns: Vec<Vec<usize>>,
+    /// Rows (0-based) recognised as center runs (every factor at its central level).
+    pub center_point_rows: Vec<usize>,
+    /// Any warnings about the data.
+    pub warnings: Vec<String>,
+}
+
+/// Dry-run summary of a file before it is imported.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    /// Detected file format ("csv", "tsv", "json", or "xlsx").
+    pub format: String,
+    /// Number of runs detected.
+    pub runs: usize,
+    /// Number of factors detected.
+    pub factors: usize,
+    /// Levels per factor.
+    pub levels: Vec<u32>,
+    /// Whether this is a mixed-level array.
+    pub is_mixed: bool,
     /// Any warnings about the data.
     pub warnings: Vec<String>,
 }
 
+/// A saved session: designs, their response data, and analyses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    /// Version of the project file format.
+    pub schema_version: u32,
+    /// Save timestamp (ISO 8601).
+    pub saved_at: String,
+    /// All designs in the project.
+    pub designs: Vec<OAData>,
+    /// Response data (runs × replicates) keyed by design ID.
+    #[serde(default)]
+    pub responses: HashMap<String, Vec<Vec<f64>>>,
+    /// Computed analyses.
+    #[serde(default)]
+    pub analyses: Vec<DOEAnalysis>,
+}
+
+/// Version and capability information about the backend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryInfo {
+    /// Application version.
+    pub app_version: String,
+    /// Linked taguchi library version.
+    pub library_version: String,
+    /// Version of the exported data formats.
+    pub schema_version: u32,
+    /// Supported construction algorithms.
+    pub constructions: Vec<String>,
+}
+
 // ========================================
 // DOE (Design of Experiments) Types
 // ========================================
@@ -206,11 +440,30 @@ pub enum OptimizationType {
     NominalIsBest,
 }
 
+/// Signal-to-Noise ratio formula.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SNFormula {
+    /// The standard Taguchi formula for the optimization type.
+    #[default]
+    Standard,
+    /// A custom variant of the standard formulas.
+    #[serde(rename_all = "camelCase")]
+    Custom {
+        /// Scale by 10·log₁₀ (power dB) when true, 20·log₁₀ when false.
+        db_scale: bool,
+        /// Use the sample variance as the noise term instead of the mean squared deviation.
+        ///
+        /// Only valid for nominal-is-best with at least two replicates per run.
+        use_variance: bool,
+    },
+}
+
 // Note: DOE config, factors, responses, measurements are managed in frontend store.
 // Only analysis request/response types are needed in Rust for the taguchi library bridge.
 
 /// Main effect analysis for a single factor.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MainEffect {
     /// Factor ID.
@@ -225,10 +478,16 @@ pub struct MainEffect {
     pub range: f64,
     /// Rank (1 = most important).
     pub rank: usize,
+    /// Level with the best mean response for the optimization type (0-indexed).
+    #[serde(default)]
+    pub best_level: usize,
+    /// Level with the worst mean response for the optimization type (0-indexed).
+    #[serde(default)]
+    pub worst_level: usize,
 }
 
 /// Signal-to-Noise ratio analysis for a single factor.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SNRatioEffect {
     /// Factor ID.
@@ -239,10 +498,13 @@ pub struct SNRatioEffect {
     pub level_sn_ratios: Vec<f64>,
     /// Level index with best S/N.
     pub optimal_level: usize,
+    /// Rank by S/N range (1 = largest range).
+    #[serde(default)]
+    pub sn_rank: usize,
 }
 
 /// ANOVA table entry for a factor.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ANOVAEntry {
     /// Factor ID.
@@ -266,7 +528,7 @@ pub struct ANOVAEntry {
 }
 
 /// Complete ANOVA results.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ANOVAResult {
     /// ANOVA entries for each factor.
@@ -281,22 +543,52 @@ pub struct ANOVAResult {
     pub total_ss: f64,
     /// Total degrees of freedom.
     pub total_df: usize,
+    /// Nested factor entries, when factor groups were given.
+    #[serde(default)]
+    pub nested: Vec<NestedANOVAEntry>,
+}
+
+/// ANOVA entry for a factor nested within a parent factor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NestedANOVAEntry {
+    /// Nested factor ID.
+    pub factor_id: String,
+    /// Nested factor name.
+    pub factor_name: String,
+    /// Parent factor ID.
+    pub parent_id: String,
+    /// Parent factor name.
+    pub parent_name: String,
+    /// Sum of squares of the nested factor within its parent.
+    pub sum_of_squares: f64,
+    /// Degrees of freedom within the parent.
+    pub degrees_of_freedom: usize,
+    /// Mean square.
+    pub mean_square: f64,
+    /// F-ratio against the error term (None if error has no degrees of freedom).
+    pub f_ratio: Option<f64>,
+    /// P-value for the F-ratio.
+    pub p_value: Option<f64>,
 }
 
 /// Confidence interval.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfidenceInterval {
     /// Lower bound.
     pub lower: f64,
     /// Upper bound.
     pub upper: f64,
+    /// Half-width, (upper - lower) / 2, for "± x" formatting.
+    #[serde(default)]
+    pub half_width: f64,
     /// Confidence level (e.g., 0.95).
     pub level: f64,
 }
 
 /// Optimal settings prediction.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OptimalSettings {
     /// Optimal level index for each factor.
@@ -305,12 +597,21 @@ pub struct OptimalSettings {
     pub predicted_mean: f64,
     /// Predicted S/N ratio.
     pub predicted_sn_ratio: f64,
+    /// Each factor's S/N gain at its optimal level; these plus `sn_grand_mean`
+    /// sum to `predicted_sn_ratio`.
+    #[serde(default)]
+    pub sn_contributions: HashMap<String, f64>,
+    /// Two-step optimization, step 1: S/N-optimal level of each control factor.
+    #[serde(default)]
+    pub sn_optimal_settings: HashMap<String, usize>,
+    /// Two-step optimization, step 2: factor to tune toward the target mean.
+    pub mean_adjustment_factor: Option<String>,
     /// Confidence interval for prediction.
     pub confidence_interval: Option<ConfidenceInterval>,
 }
 
 /// Complete DOE analysis results.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DOEAnalysis {
     /// Reference to DOEConfig.
@@ -319,6 +620,9 @@ pub struct DOEAnalysis {
     pub grand_mean: f64,
     /// Grand mean of all S/N ratios.
     pub sn_grand_mean: f64,
+    /// The S/N formula applied, e.g. "larger-is-better: -10·log(mean(1/y²))".
+    #[serde(default)]
+    pub sn_formula_used: String,
     /// Main effects for each factor.
     pub main_effects: Vec<MainEffect>,
     /// S/N ratio effects for each factor.
@@ -327,8 +631,247 @@ pub struct DOEAnalysis {
     pub anova: ANOVAResult,
     /// Optimal settings.
     pub optimal_settings: OptimalSettings,
+    /// Share of the run-mean variation explained by the unpooled factors.
+    pub r_squared: Option<f64>,
+    /// R² adjusted for the model's degrees of freedom.
+    pub adj_r_squared: Option<f64>,
     /// Analysis timestamp (ISO 8601).
     pub analyzed_at: String,
+    /// SHA-256 of the array data, response data, and analysis settings.
+    #[serde(default)]
+    pub input_hash: String,
+    /// Caveats about the analysis the user should be aware of.
+    #[serde(default)]
+    pub analysis_warnings: Vec<String>,
+}
+
+/// Result of checking a DOE setup before analysis.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoeValidation {
+    /// Whether the analysis can run.
+    pub valid: bool,
+    /// Problems that would make the analysis fail.
+    pub errors: Vec<String>,
+    /// Problems that weaken the analysis.
+    pub warnings: Vec<String>,
+}
+
+/// Standard errors of a factor's main effect.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorSE {
+    /// Factor ID.
+    pub factor_id: String,
+    /// Factor name.
+    pub factor_name: String,
+    /// Standard error of the mean response at each level (NaN for levels with no runs).
+    pub level_standard_errors: Vec<f64>,
+    /// Standard error of the difference between two level means, assuming
+    /// equal runs per level.
+    pub effect_standard_error: f64,
+    /// Error degrees of freedom behind the estimates (for t-statistics).
+    pub error_df: usize,
+}
+
+/// Main-effects plot data with a shared decision band around the grand mean.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectsPlotBands {
+    /// Grand mean of all responses (band centre).
+    pub grand_mean: f64,
+    /// Least significant difference (band half-width).
+    pub lsd: f64,
+    /// Lower edge of the band.
+    pub lower: f64,
+    /// Upper edge of the band.
+    pub upper: f64,
+    /// Confidence level used for the band.
+    pub confidence_level: f64,
+    /// Level means for each factor.
+    pub factors: Vec<EffectsPlotFactor>,
+}
+
+/// One factor's level means in an effects plot.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectsPlotFactor {
+    /// Factor ID.
+    pub factor_id: String,
+    /// Factor name.
+    pub factor_name: String,
+    /// Mean response at each level.
+    pub level_means: Vec<f64>,
+    /// Levels (0-based) whose means fall outside the band.
+    pub significant_levels: Vec<usize>,
+}
+
+/// Structural checks on array and response data before analysis.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecheckReport {
+    /// Whether `run_doe_analysis` can build an OA from this data.
+    pub ready: bool,
+    /// Whether the array is non-empty and every row has the same number of columns.
+    pub shape_consistent: bool,
+    /// Whether there is one non-empty, finite response row per array row.
+    pub responses_aligned: bool,
+    /// Whether every level of every factor appears equally often.
+    pub balanced: bool,
+    /// Detected number of levels per factor (empty if the shape is inconsistent).
+    pub levels: Vec<u32>,
+    /// Problems that would make the analysis fail.
+    pub errors: Vec<String>,
+    /// Problems that weaken the analysis.
+    pub warnings: Vec<String>,
+}
+
+/// A factor's main effect split into orthogonal polynomial components.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolynomialContrasts {
+    /// Factor ID.
+    pub factor_id: String,
+    /// Factor name.
+    pub factor_name: String,
+    /// One component per degree, from linear up to levels - 1.
+    pub components: Vec<PolynomialComponent>,
+    /// Error degrees of freedom of the F-tests.
+    pub error_df: usize,
+    /// Any warnings about the decomposition.
+    pub warnings: Vec<String>,
+}
+
+/// One orthogonal polynomial component of a main effect.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolynomialComponent {
+    /// Polynomial degree (1 = linear, 2 = quadratic, ...).
+    pub degree: usize,
+    /// Component name ("Linear", "Quadratic", ...).
+    pub name: String,
+    /// Contrast coefficient for each level.
+    pub coefficients: Vec<f64>,
+    /// Contrast value (coefficient-weighted sum of level means).
+    pub contrast: f64,
+    /// Sum of squares (1 degree of freedom).
+    pub sum_of_squares: f64,
+    /// F-ratio against the error term (None if error has no degrees of freedom).
+    pub f_ratio: Option<f64>,
+    /// P-value for the F-ratio.
+    pub p_value: Option<f64>,
+}
+
+/// Range-based importance of a factor.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorImportance {
+    /// Factor ID.
+    pub factor_id: String,
+    /// Factor name.
+    pub factor_name: String,
+    /// Max - min of the factor's level means.
+    pub range: f64,
+    /// Range as a percentage of the sum of all factor ranges.
+    pub percent: f64,
+}
+
+/// Between-run and within-run variance from a one-way random-effects model.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VarianceComponents {
+    /// Variance between runs (run-to-run variation).
+    pub between_run: f64,
+    /// Variance within runs (repeatability).
+    pub within_run: f64,
+    /// Sum of both components.
+    pub total: f64,
+    /// Between-run share of the total, in percent.
+    pub between_run_percent: f64,
+    /// Within-run share of the total, in percent.
+    pub within_run_percent: f64,
+}
+
+/// Single-degree-of-freedom test for curvature using center runs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurvatureTest {
+    /// Rows (0-based) used as center runs.
+    pub center_rows: Vec<usize>,
+    /// Number of factorial (non-center) runs.
+    pub factorial_runs: usize,
+    /// Mean response of the factorial runs.
+    pub factorial_mean: f64,
+    /// Mean response of the center runs.
+    pub center_mean: f64,
+    /// Sum of squares for curvature.
+    pub sum_of_squares: f64,
+    /// Pure-error mean square from the spread of the center runs.
+    pub error_mean_square: f64,
+    /// Pure-error degrees of freedom (center runs - 1).
+    pub error_df: usize,
+    /// F statistic on 1 and `error_df` degrees of freedom.
+    pub f_value: f64,
+    /// Upper-tail p-value of the F statistic.
+    pub p_value: f64,
+}
+
+/// Recommendation on whether to pool a factor into the error term.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolingSuggestion {
+    /// Factor ID.
+    pub factor_id: String,
+    /// Factor name.
+    pub factor_name: String,
+    /// Contribution percentage before pooling.
+    pub contribution_percent: f64,
+    /// Degrees of freedom of the factor.
+    pub degrees_of_freedom: usize,
+    /// Whether this factor is recommended for pooling.
+    pub pool: bool,
+    /// Error degrees of freedom if this and all smaller factors are pooled.
+    pub error_df: usize,
+}
+
+/// Two-factor means grid for an interaction plot.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InteractionPlotData {
+    /// First factor ID (rows of the grid).
+    pub factor_a_id: String,
+    /// First factor name.
+    pub factor_a_name: String,
+    /// Second factor ID (columns of the grid).
+    pub factor_b_id: String,
+    /// Second factor name.
+    pub factor_b_name: String,
+    /// Mean response at each (level_a, level_b) combination, if observed.
+    pub means: Vec<Vec<Option<f64>>>,
+    /// Number of runs at each (level_a, level_b) combination.
+    pub counts: Vec<Vec<usize>>,
+}
+
+/// Taguchi response table: mean response at each level of each factor.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTable {
+    /// Grand mean of all run averages.
+    pub grand_mean: f64,
+    /// One row per factor, in column order.
+    pub factors: Vec<ResponseTableRow>,
+}
+
+/// A single factor's row in the response table.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTableRow {
+    /// Factor ID.
+    pub factor_id: String,
+    /// Factor name.
+    pub factor_name: String,
+    /// Mean response at each level.
+    pub level_means: Vec<f64>,
 }
 
 /// Request for DOE analysis.
@@ -337,8 +880,8 @@ pub struct DOEAnalysis {
 pub struct DOEAnalysisRequest {
     /// The OA matrix (runs × factors).
     pub array_data: Vec<Vec<u32>>,
-    /// Response data (runs × replicates).
-    pub response_data: Vec<Vec<f64>>,
+    /// Response data (runs × replicates, or one mean per run).
+    pub response_data: ResponseData,
     /// Factor IDs in column order.
     pub factor_ids: Vec<String>,
     /// Factor names in column order.
@@ -355,4 +898,76 @@ pub struct DOEAnalysisRequest {
     pub min_unpooled_factors: Option<usize>,
     /// Confidence level for intervals (default: 0.95).
     pub confidence_level: Option<f64>,
+    /// S/N ratio formula (default: standard Taguchi formulas).
+    pub sn_formula: Option<SNFormula>,
+    /// Nested factors: parent factor ID -> IDs of factors nested within it.
+    pub factor_groups: Option<HashMap<String, Vec<String>>>,
+    /// ANOVA entry order: "factor" (default), "contribution", or "f_ratio".
+    pub sort_by: Option<String>,
+}
+
+/// Response data - replicate measurements or a single mean per run.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ResponseData {
+    /// Replicate measurements for each run (runs × replicates).
+    Replicated(Vec<Vec<f64>>),
+    /// A single averaged response per run (no replicates).
+    RunMeans(Vec<f64>),
+}
+
+impl ResponseData {
+    /// Convert to the runs × replicates layout used by the analysis library.
+    pub fn to_replicates(&self) -> Vec<Vec<f64>> {
+        match self {
+            ResponseData::Replicated(data) => data.clone(),
+            ResponseData::RunMeans(means) => means.iter().map(|&m| vec![m]).collect(),
+        }
+    }
+}
+
+/// Degrees of freedom needed by a model compared with those a design offers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DofBudget {
+    /// DOF of the main effects (sum of levels - 1).
+    pub factor_dof: usize,
+    /// DOF of the requested two-factor interactions.
+    pub interaction_dof: usize,
+    /// Total DOF the model needs.
+    pub required_dof: usize,
+    /// DOF the candidate array offers (runs - 1).
+    pub available_dof: usize,
+    /// Fewest runs that could estimate the model (required DOF + 1).
+    pub min_runs: usize,
+    /// Whether the candidate array has enough DOF.
+    pub feasible: bool,
+}
+
+/// Budget estimate for running a design.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentEstimate {
+    /// Number of runs in the design.
+    pub runs: usize,
+    /// Total cost of all runs.
+    pub total_cost: f64,
+    /// Total time of all runs, in minutes.
+    pub total_time_minutes: f64,
+    /// Per-block breakdown; empty when the design is not blocked.
+    pub blocks: Vec<BlockEstimate>,
+}
+
+/// Budget estimate for one block of runs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockEstimate {
+    /// Block number (1-based).
+    pub block: usize,
+    /// Number of runs in the block.
+    pub runs: usize,
+    /// Cost of the block's runs.
+    pub cost: f64,
+    /// Time of the block's runs, in minutes.
+    pub time_minutes: f64,
 }
@@ -4,7 +4,7 @@
  */
 import { invoke } from '@tauri-apps/api/core';
 import { RuneStore } from 'tauri-plugin-svelte';
-import type { OAData, BuildRequest, ConstructionOption, ValidationResult } from '$lib/types';
+import type { OAData, BuildRequest, BuildError, ConstructionOption, ValidationResult } from '$lib/types';
 
 // Serializable state for persistence (requires index signature for State constraint)
 interface ArrayStoreState {
@@ -161,7 +161,7 @@ export const arrayStore = {
       await saveState();
       return result;
     } catch (e) {
-      _error = String(e);
+      _error = (e as BuildError)?.message ?? String(e);
       throw e;
     } finally {
       _loading = false;
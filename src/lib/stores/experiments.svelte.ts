@@ -3,7 +3,7 @@
  */
 import { invoke } from '@tauri-apps/api/core';
 import { save, open } from '@tauri-apps/plugin-dialog';
-import type { OAData, ImportValidation } from '$lib/types';
+import type { OAData, ImportError, ImportValidation } from '$lib/types';
 
 // Store state
 let _recentExports = $state<string[]>([]);
@@ -86,7 +86,7 @@ export const experimentsStore = {
     _error = null;
     try {
       const path = await open({
-        filters: [{ name: 'CSV', extensions: ['csv'] }],
+        filters: [{ name: 'CSV', extensions: ['csv', 'gz'] }],
         multiple: false,
       });
 
@@ -108,7 +108,7 @@ export const experimentsStore = {
     _error = null;
     try {
       const path = await open({
-        filters: [{ name: 'JSON', extensions: ['json'] }],
+        filters: [{ name: 'JSON', extensions: ['json', 'gz'] }],
         multiple: false,
       });
 
@@ -117,7 +117,7 @@ export const experimentsStore = {
       }
       return null;
     } catch (e) {
-      _error = String(e);
+      _error = (e as ImportError)?.message ?? String(e);
       throw e;
     } finally {
       _loading = false;
@@ -14,6 +14,7 @@ export interface OAData {
   strength: number;
   data: number[][];
   metadata: OAMetadata;
+  warnings?: string[];               // Non-fatal build/import warnings
 }
 
 export interface OAMetadata {
@@ -27,14 +28,17 @@ export interface BuildRequest {
   levels: number | number[];
   factors: number;
   strength: number;
+  maxStrength?: number;              // Try up to this strength within the run budget
   minRuns?: number;
   algorithm?: string;
+  mode?: 'orthogonal' | 'covering';  // Covering: every t-tuple at least once (default orthogonal)
 }
 
 export interface ConstructionOption {
   name: string;
   runs: number;
   maxFactors: number;
+  relativeSize: number;              // Runs / smallest option runs
   description: string;
   constraints: string[];
 }
@@ -46,6 +50,18 @@ export interface ValidationResult {
   suggestions: ConstructionOption[];
 }
 
+export interface BuildError {
+  message: string;                   // Includes the closest options, if any
+  alternatives: BuildAlternative[];  // Closest feasible constructions, best first
+}
+
+export interface BuildAlternative {
+  name: string;
+  runs: number;
+  maxFactors: number;
+  strength: number;
+}
+
 // ========================================
 // Analysis Types
 // ========================================
@@ -73,6 +89,7 @@ export interface BalanceData {
   factorBalance: boolean[];
   levelCounts: Record<number, number>[];
   expectedCount: number;
+  entropy: number[];                 // Normalized level entropy (1 = uniform)
 }
 
 export interface CorrelationData {
@@ -98,12 +115,20 @@ export interface Experiment {
 // Import/Export Types
 // ========================================
 
+export interface ImportError {
+  line?: number;                     // 1-based line, if known
+  column?: number;                   // 1-based column, if known
+  message: string;
+}
+
 export interface ImportValidation {
   runs: number;
   factors: number;
   levels: number[];
   isMixed: boolean;
   estimatedStrength: number;
+  duplicateColumns: number[][];      // Columns identical up to relabeling
+  centerPointRows: number[];         // Rows with every factor at its middle level
   warnings: string[];
 }
 
@@ -148,6 +173,11 @@ export interface NavSection {
 /** Optimization goal for response variable */
 export type OptimizationType = 'larger-is-better' | 'smaller-is-better' | 'nominal-is-best';
 
+/** S/N ratio formula (standard Taguchi or a custom variant) */
+export type SNFormula =
+  | { type: 'standard' }
+  | { type: 'custom'; dbScale: boolean; useVariance: boolean };
+
 /** Factor configuration for an experiment */
 export interface ExperimentFactor {
   id: string;
@@ -216,6 +246,8 @@ export interface MainEffect {
   levelEffects: number[];            // Effect = mean - grand mean
   range: number;                     // Max - Min (factor importance)
   rank: number;                      // 1 = most important
+  bestLevel: number;                 // Best level for the optimization type (0-indexed)
+  worstLevel: number;                // Worst level for the optimization type (0-indexed)
 }
 
 /** Signal-to-Noise ratio analysis for a single factor */
@@ -224,6 +256,7 @@ export interface SNRatioEffect {
   factorName: string;
   levelSnRatios: number[];           // S/N ratio at each level (dB) - camelCase from Rust
   optimalLevel: number;              // Level index with best S/N
+  snRank: number;                    // 1 = largest S/N range
 }
 
 /** ANOVA table entry for a factor */
@@ -247,6 +280,20 @@ export interface ANOVAResult {
   errorMs: number;
   totalSs: number;
   totalDf: number;
+  nested: NestedANOVAEntry[];            // Factors nested within a parent
+}
+
+/** ANOVA entry for a factor nested within a parent factor */
+export interface NestedANOVAEntry {
+  factorId: string;
+  factorName: string;
+  parentId: string;
+  parentName: string;
+  sumOfSquares: number;
+  degreesOfFreedom: number;
+  meanSquare: number;
+  fRatio?: number;
+  pValue?: number;
 }
 
 /** Optimal settings prediction */
@@ -254,9 +301,13 @@ export interface OptimalSettings {
   factorLevels: Record<string, number>;  // factorId -> level index
   predictedMean: number;
   predictedSnRatio: number;              // Note: camelCase from Rust's snake_case
+  snContributions: Record<string, number>; // factorId -> S/N gain at optimal level
+  snOptimalSettings: Record<string, number>; // Two-step: control factorId -> S/N-optimal level
+  meanAdjustmentFactor?: string;         // Two-step: factorId to tune toward the target mean
   confidenceInterval?: {
     lower: number;
     upper: number;
+    halfWidth: number;                    // (upper - lower) / 2
     level: number;                        // e.g., 0.95 for 95% CI
   };
 }
@@ -266,17 +317,22 @@ export interface DOEAnalysis {
   configId: string;
   grandMean: number;
   snGrandMean: number;               // Grand mean of all S/N ratios
+  snFormulaUsed: string;             // S/N formula applied
   mainEffects: MainEffect[];
   snRatioEffects: SNRatioEffect[];
   anova: ANOVAResult;
   optimalSettings: OptimalSettings;
+  rSquared?: number;                 // Variation explained by unpooled factors
+  adjRSquared?: number;              // R² adjusted for model DOF
   analyzedAt: string;
+  inputHash: string;                 // SHA-256 of the analysis inputs
+  analysisWarnings: string[];        // Caveats about the analysis
 }
 
 /** Request payload for DOE analysis */
 export interface DOEAnalysisRequest {
   arrayData: number[][];             // The OA matrix
-  responseData: number[][];          // Runs × Replicates
+  responseData: number[][] | number[]; // Runs × Replicates, or one mean per run
   factorIds: string[];               // Factor IDs in order
   factorNames: string[];             // Factor names in order
   optimizationType: OptimizationType;
@@ -285,4 +341,7 @@ export interface DOEAnalysisRequest {
   enablePooling?: boolean;           // Whether to pool factors (default true)
   minUnpooledFactors?: number;       // Min factors to keep unpooled (default 1)
   confidenceLevel?: number;          // For CI (default 0.95)
+  snFormula?: SNFormula;             // S/N formula (default standard)
+  factorGroups?: Record<string, string[]>; // Parent factor ID -> nested factor IDs
+  sortBy?: 'factor' | 'contribution' | 'f_ratio'; // ANOVA entry order (default factor)
 }